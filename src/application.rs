@@ -80,6 +80,8 @@ impl Scion {
                 window: None,
                 main_thread_receiver: None,
                 scion_pre_renderer: Default::default(),
+                running: true,
+                event_loop_proxy: None,
             }.launch_game_loop();
         } else {
             // Game is running in a window, it must be created & handled in the main thread, so
@@ -95,7 +97,8 @@ impl Scion {
                 .expect("An error occured while building the main game window"));
             let window_rendering_manager = futures::executor::block_on(ScionWindowRenderingManager::new(window.clone(), self.config.window_config.as_ref().unwrap().default_background_color.clone()));
             let (event_sender, receiver) = mpsc::channel::<WindowingEvent>();
-            thread::spawn(move || {
+            let event_loop_proxy = event_loop.create_proxy();
+            let game_thread = thread::spawn(move || {
                 ScionRunner {
                     game_data: self.game_data,
                     scheduler: self.scheduler,
@@ -104,13 +107,20 @@ impl Scion {
                     window: Some(window.clone()),
                     main_thread_receiver: Some(receiver),
                     scion_pre_renderer: Default::default(),
+                    running: true,
+                    event_loop_proxy: Some(event_loop_proxy),
                 }.launch_game_loop();
             });
-            let _result = event_loop.run(move |event, loopd| {
+            let _result = event_loop.run(|event, loopd| {
                 match event {
                     Event::WindowEvent { event, window_id: _ } => {
                         match event {
-                            WindowEvent::CloseRequested => loopd.exit(),
+                            WindowEvent::CloseRequested => {
+                                // The game decides when to quit, unless its loop is not running anymore
+                                if event_sender.send(WindowingEvent { window_event: Some(WindowEvent::CloseRequested), redraw: false }).is_err() {
+                                    loopd.exit();
+                                }
+                            }
                             WindowEvent::RedrawRequested => {
                                 let _r = event_sender.send(WindowingEvent { window_event: Some(WindowEvent::RedrawRequested), redraw: true });
                             }
@@ -119,12 +129,16 @@ impl Scion {
                             }
                         }
                     }
+                    Event::UserEvent(()) => loopd.exit(),
                     Event::AboutToWait => {
                         //
                     }
                     _ => {}
                 }
             });
+            // Dropping the sender lets the game thread stop its loop and run its shutdown hooks if it is still running
+            drop(event_sender);
+            let _r = game_thread.join();
         }
     }
 }
@@ -18,6 +18,12 @@ pub struct ScionConfig {
     pub(crate) logger_config: Option<LoggerConfig>,
     /// Window configuration to use.
     pub(crate) window_config: Option<WindowConfig>,
+    /// Number of fixed updates per second. Defaults to 60.
+    pub(crate) fixed_update_rate: Option<u32>,
+    /// Seed of the `Random` resource. Defaults to a seed computed from the current time.
+    pub(crate) random_seed: Option<u64>,
+    /// Whether the `LifecycleEvent`s are published into the `Events`. Defaults to false.
+    pub(crate) lifecycle_events: Option<bool>,
 }
 
 impl Default for ScionConfig {
@@ -26,6 +32,9 @@ impl Default for ScionConfig {
             app_name: "Scion game".to_string(),
             logger_config: Some(Default::default()),
             window_config: Some(Default::default()),
+            fixed_update_rate: None,
+            random_seed: None,
+            lifecycle_events: None,
         }
     }
 }
@@ -65,6 +74,24 @@ impl ScionConfigBuilder {
         self
     }
 
+    /// Sets the number of fixed updates per second, used by fixed systems and `Scene::on_fixed_update`
+    pub fn with_fixed_update_rate(mut self, fixed_update_rate: u32) -> Self {
+        self.config.fixed_update_rate = Some(fixed_update_rate);
+        self
+    }
+
+    /// Sets the seed of the `Random` resource, to get the same random numbers on each run
+    pub fn with_random_seed(mut self, random_seed: u64) -> Self {
+        self.config.random_seed = Some(random_seed);
+        self
+    }
+
+    /// Publishes a `LifecycleEvent` into the `Events` each time an entity or a component is created or deleted
+    pub fn with_lifecycle_events(mut self, lifecycle_events: bool) -> Self {
+        self.config.lifecycle_events = Some(lifecycle_events);
+        self
+    }
+
     /// Retrieves the configuration built
     pub fn get(self) -> ScionConfig {
         self.config
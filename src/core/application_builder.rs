@@ -1,10 +1,17 @@
 use crate::config::scion_config::ScionConfig;
+use crate::core::headless_runner::HeadlessRunner;
 use crate::core::package::Package;
-use crate::core::scene::{Scene, SceneMachine};
-use crate::core::scheduler::Scheduler;
+use crate::core::scene::{Scene, SceneFactory, SceneMachine};
+use crate::core::resources::save_manager::SaveManager;
+use crate::core::resources::time::Time;
+use crate::core::resources::window::Window;
+use crate::core::scion_runner::ScionRunner;
+use crate::core::scheduler::{ExecutionMode, RunCriteria, Scheduler, Stage, System, SystemConfig};
 use crate::core::state::GameState;
 use crate::core::systems::InternalPackage;
+use crate::core::systems::tween_system::tween_system;
 use crate::core::world::GameData;
+use crate::graphics::components::tween::Lens;
 use crate::Scion;
 
 /// Builder providing convenience functions to build the `Scion` application.
@@ -14,38 +21,95 @@ pub struct ScionBuilder {
     config: ScionConfig,
     scheduler: Scheduler,
     scene: Option<Box<dyn Scene + Send>>,
+    loading_scene: Option<SceneFactory>,
     world: GameData,
 }
 
 impl ScionBuilder {
     pub fn new(config: ScionConfig) -> Self {
-        let builder = Self {
+        let fixed_update_rate = config.fixed_update_rate.unwrap_or(60);
+        let mut builder = Self {
             config,
             scheduler: Default::default(),
             scene: Default::default(),
+            loading_scene: None,
             world: Default::default(),
-        };
-        builder.with_package(InternalPackage)
+        }.with_package(InternalPackage);
+        builder.world
+            .get_resource_mut::<Time>()
+            .expect("Time is an internal resource and can't be missing")
+            .set_fixed_update_rate(fixed_update_rate);
+        if let Some(seed) = builder.config.random_seed {
+            builder.world.random().reseed(seed);
+        }
+        if builder.config.lifecycle_events.unwrap_or(false) {
+            builder.world.subworld.enable_lifecycle_events();
+        }
+        let save_manager = SaveManager::new(&builder.config.app_name);
+        builder.world.insert_resource(save_manager);
+        builder
     }
 
     /// Specify a system to add to the scheduler.
-    pub fn with_system(mut self, system: fn(&mut GameData)) -> Self {
+    /// Accepts functions, closures (`|data: &mut GameData| {}`) and any type implementing [`System`].
+    pub fn with_system<S: System>(mut self, system: S) -> Self {
         self.scheduler.add_system(system);
         self
     }
 
+    /// Specify a system to execute at the fixed update rate (see [`Stage::FixedUpdate`]).
+    /// Use [`Time::fixed_delta_duration`] as the delta of its computations.
+    pub fn with_fixed_system<S: System>(mut self, system: S) -> Self {
+        self.scheduler.add_system_config(SystemConfig::new(system).in_stage(Stage::FixedUpdate));
+        self
+    }
+
+    /// Plays the [`crate::graphics::components::tween::Tween`]s using the lens `L`. Only needed for the user-defined lenses, the tweens of the engine's
+    /// lenses are already played.
+    pub fn with_tween_lens<L: Lens>(mut self) -> Self {
+        self.scheduler.add_system_config(SystemConfig::new(tween_system::<L>).in_stage(Stage::PreUpdate).label("tween_system"));
+        self
+    }
+
     /// Specify a system to add to the scheduler with a conditional pausing flag function.
-    pub fn with_pausable_system(mut self, system: fn(&mut GameData), pause_condition: fn(&GameState) -> bool) -> Self {
+    pub fn with_pausable_system<S: System>(mut self, system: S, pause_condition: fn(&GameState) -> bool) -> Self {
         self.scheduler.add_pausable_system(system, pause_condition);
         self
     }
 
+    /// Specify a system to add to the scheduler, only executed when `criteria` is met.
+    pub fn with_conditional_system<S: System>(mut self, system: S, criteria: RunCriteria) -> Self {
+        self.scheduler.add_system_config(SystemConfig::new(system).run_if(criteria));
+        self
+    }
+
+    /// Specify a system to add to the scheduler, using a [`SystemConfig`] to define its stage, label
+    /// and ordering constraints. The final order is resolved when calling [`ScionBuilder::run`].
+    pub fn with_system_config(mut self, config: SystemConfig) -> Self {
+        self.scheduler.add_system_config(config);
+        self
+    }
+
+    /// Sets how the parallel systems are executed. Use [`ExecutionMode::SingleThreaded`] to get a
+    /// deterministic execution on the game thread while debugging.
+    pub fn with_execution_mode(mut self, mode: ExecutionMode) -> Self {
+        self.scheduler.set_execution_mode(mode);
+        self
+    }
+
     /// Set the scene to the given one. Only one scene can be executed at a time
     pub fn with_scene<T: Scene + Default + Send + 'static>(mut self) -> Self {
         self.scene = Some(Box::<T>::default());
         self
     }
 
+    /// Set the scene displayed while the assets declared by a scene (see [`Scene::assets`]) are loaded.
+    /// It can display the loading progress using the [`crate::core::scene::loading::LoadingProgress`] resource.
+    pub fn with_loading_scene<T: Scene + Default + Send + 'static>(mut self) -> Self {
+        self.loading_scene = Some(|| Box::<T>::default());
+        self
+    }
+
     ///
     pub fn with_package<P: Package>(mut self, package: P) -> Self {
         package.prepare(&mut self.world);
@@ -53,13 +117,41 @@ impl ScionBuilder {
     }
 
     /// Builds, setups and runs the Scion application, must be called at the end of the building process.
-    pub fn run(self) {
+    /// Panics if the systems ordering constraints can't be resolved.
+    pub fn run(mut self) {
+        self.resolve_order();
         let scion = Scion {
             config: self.config,
             game_data: self.world,
             scheduler: self.scheduler,
-            layer_machine: SceneMachine { current_scene: self.scene, current_scene_started: false },
+            layer_machine: SceneMachine::new(self.scene).with_loading_scene(self.loading_scene),
         };
         scion.run();
     }
-}
\ No newline at end of file
+
+    /// Builds and setups the Scion application without window nor rendering. Frames are then executed
+    /// on demand with a virtual clock using the returned [`HeadlessRunner`].
+    /// Panics if the systems ordering constraints can't be resolved.
+    pub fn build_headless(mut self) -> HeadlessRunner {
+        self.resolve_order();
+        let dimensions = self.config.window_config.as_ref().and_then(|config| config.dimensions).unwrap_or((0, 0));
+        self.world.insert_resource(Window::new(dimensions, 1.));
+        HeadlessRunner::new(ScionRunner {
+            game_data: self.world,
+            scheduler: self.scheduler,
+            layer_machine: SceneMachine::new(self.scene).with_loading_scene(self.loading_scene),
+            window_rendering_manager: None,
+            window: None,
+            main_thread_receiver: None,
+            scion_pre_renderer: Default::default(),
+            running: true,
+            event_loop_proxy: None,
+        })
+    }
+
+    fn resolve_order(&mut self) {
+        if let Err(e) = self.scheduler.resolve_order() {
+            panic!("Impossible to resolve the systems execution order: {}", e);
+        }
+    }
+}
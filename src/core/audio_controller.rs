@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 use std::io::BufReader;
-use std::sync::mpsc::Receiver;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
 use std::time::Duration;
 
 use log::debug;
@@ -23,8 +23,8 @@ pub(crate) fn audio_thread(controller: AudioController) {
     let mut sinks: HashMap<usize, Sink> = HashMap::new();
 
     loop {
-        if let Ok(message) = controller.receiver.recv_timeout(Duration::from_secs(1)) {
-            match message {
+        match controller.receiver.recv_timeout(Duration::from_secs(1)) {
+            Ok(message) => match message {
                 AudioEvent::PlaySound { path, config, sound_id } => {
                     debug!("Started to play sound {}", path);
                     let sink = Sink::try_new(&stream_handle).unwrap();
@@ -45,10 +45,14 @@ pub(crate) fn audio_thread(controller: AudioController) {
                         drop(sink);
                     }
                 }
-            }
+                AudioEvent::Shutdown => break,
+            },
+            Err(RecvTimeoutError::Disconnected) => break,
+            Err(RecvTimeoutError::Timeout) => {}
         }
         sinks.retain(|&_k, sink| {
             !sink.empty()
         });
     }
+    sinks.values().for_each(|sink| sink.stop());
 }
@@ -0,0 +1,274 @@
+//! Detection of the changes made to the entities of the world: per system trackers of the components of a type,
+//! and lifecycle events published into [`crate::core::resources::events::Events`].
+
+use std::any::TypeId;
+use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+
+use hecs::{Component, Entity};
+
+use crate::core::world::{GameData, World};
+
+/// `ChangeTracker` detects the entities whose component `T` was added, changed or removed between two updates.
+/// Each system owns its trackers, and updates them when it runs to get the changes made since it last ran.
+/// The first update reports every existing component as added.
+/// A tracker doesn't spare the scan of the world : each update iterates over every component `T`, and
+/// [`ChangeTracker::update`] also clones them to compare them at the next update. Prefer
+/// [`ChangeTracker::update_presence`] when only additions and removals matter, or the [`LifecycleEvent`]s, recorded when
+/// the world is modified, to be notified of them without any scan.
+/// ```
+/// use scion::core::change_detection::{Changed, ChangeTracker};
+/// use scion::core::components::maths::transform::Transform;
+/// use scion::core::scheduler::System;
+/// use scion::core::world::GameData;
+///
+/// #[derive(Default)]
+/// struct MovesLogger {
+///     transforms: ChangeTracker<Transform>,
+/// }
+///
+/// impl System for MovesLogger {
+///     fn run(&mut self, data: &mut GameData) {
+///         self.transforms.update(data);
+///         for entity in self.transforms.query::<Changed<Transform>>() {
+///             println!("{:?} moved", entity);
+///         }
+///     }
+/// }
+/// ```
+pub struct ChangeTracker<T> {
+    present: HashSet<Entity>,
+    values: HashMap<Entity, T>,
+    added: Vec<Entity>,
+    changed: Vec<Entity>,
+    removed: Vec<Entity>,
+}
+
+impl<T: Component> Default for ChangeTracker<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Component> ChangeTracker<T> {
+    pub fn new() -> Self {
+        Self {
+            present: HashSet::new(),
+            values: HashMap::new(),
+            added: Vec::new(),
+            changed: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+
+    /// Detects the entities whose component `T` was added or removed since the previous update, without
+    /// detecting the changed ones. Useful for components that can't be compared or cloned.
+    pub fn update_presence(&mut self, data: &GameData) {
+        let present: HashSet<Entity> = data.query::<&T>().iter().map(|(entity, _)| entity).collect();
+        self.values.clear();
+        self.changed.clear();
+        self.added = present.difference(&self.present).copied().collect();
+        self.removed = self.present.difference(&present).copied().collect();
+        self.present = present;
+    }
+
+    /// Entities whose component `T` was added since the previous update
+    pub fn added(&self) -> &[Entity] {
+        &self.added
+    }
+
+    /// Entities whose component `T` was modified since the previous update
+    pub fn changed(&self) -> &[Entity] {
+        &self.changed
+    }
+
+    /// Entities whose component `T` was removed, or that were despawned, since the previous update
+    pub fn removed(&self) -> &[Entity] {
+        &self.removed
+    }
+
+    /// Entities matching the filter `F`, one of [`Added`], [`Changed`] or [`Removed`]
+    pub fn query<F: ChangeFilter<T>>(&self) -> &[Entity] {
+        F::entities(self)
+    }
+}
+
+impl<T: Component + Clone + PartialEq> ChangeTracker<T> {
+    /// Detects the entities whose component `T` was added, changed or removed since the previous update.
+    /// Every component `T` is cloned, to be compared with its value at the next update.
+    pub fn update(&mut self, data: &GameData) {
+        let values: HashMap<Entity, T> =
+            data.query::<&T>().iter().map(|(entity, component)| (entity, component.clone())).collect();
+        self.added.clear();
+        self.changed.clear();
+        values.iter().for_each(|(entity, value)| match self.values.get(entity) {
+            None => self.added.push(*entity),
+            Some(previous) if previous != value => self.changed.push(*entity),
+            Some(_) => {}
+        });
+        self.removed = self.present.iter().filter(|entity| !values.contains_key(entity)).copied().collect();
+        self.present = values.keys().copied().collect();
+        self.values = values;
+    }
+}
+
+/// Filter of the changes reported by a [`ChangeTracker`]
+pub trait ChangeFilter<T> {
+    fn entities(tracker: &ChangeTracker<T>) -> &[Entity];
+}
+
+/// Filters the entities whose component `T` was added
+pub struct Added<T>(PhantomData<T>);
+
+/// Filters the entities whose component `T` was modified
+pub struct Changed<T>(PhantomData<T>);
+
+/// Filters the entities whose component `T` was removed
+pub struct Removed<T>(PhantomData<T>);
+
+impl<T: Component> ChangeFilter<T> for Added<T> {
+    fn entities(tracker: &ChangeTracker<T>) -> &[Entity] {
+        tracker.added()
+    }
+}
+
+impl<T: Component> ChangeFilter<T> for Changed<T> {
+    fn entities(tracker: &ChangeTracker<T>) -> &[Entity] {
+        tracker.changed()
+    }
+}
+
+impl<T: Component> ChangeFilter<T> for Removed<T> {
+    fn entities(tracker: &ChangeTracker<T>) -> &[Entity] {
+        tracker.removed()
+    }
+}
+
+/// `LifecycleEvent` is published into the typed channel of the `Events` each time an entity or a component is created
+/// or deleted, when enabled with `ScionConfigBuilder::with_lifecycle_events`. Spawning an entity also publishes an
+/// added event for each of its components, and despawning it a removed event for each of its components.
+/// ```no_run
+/// use scion::core::change_detection::LifecycleEvent;
+/// use scion::core::components::maths::transform::Transform;
+/// use scion::core::resources::events::PollConfiguration;
+/// use scion::core::world::GameData;
+///
+/// fn spawn_logger(data: &mut GameData) {
+///     let subscriber = data.events().channel::<LifecycleEvent>().subscribe(PollConfiguration::default());
+///     // Next frames
+///     for event in data.events().channel::<LifecycleEvent>().poll(&subscriber).unwrap() {
+///         if let LifecycleEvent::ComponentAdded(entity, _) = event {
+///             if event.concerns::<Transform>() {
+///                 println!("{:?} has now a transform", entity);
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LifecycleEvent {
+    EntitySpawned(Entity),
+    EntityDespawned(Entity),
+    /// A component, identified by its type id, was added to the entity
+    ComponentAdded(Entity, TypeId),
+    /// A component, identified by its type id, was removed from the entity
+    ComponentRemoved(Entity, TypeId),
+}
+
+impl LifecycleEvent {
+    /// Entity concerned by this event
+    pub fn entity(&self) -> Entity {
+        match self {
+            LifecycleEvent::EntitySpawned(entity)
+            | LifecycleEvent::EntityDespawned(entity)
+            | LifecycleEvent::ComponentAdded(entity, _)
+            | LifecycleEvent::ComponentRemoved(entity, _) => *entity,
+        }
+    }
+
+    /// Returns true if this event is about a component of type `T`
+    pub fn concerns<T: Component>(&self) -> bool {
+        match self {
+            LifecycleEvent::ComponentAdded(_, type_id) | LifecycleEvent::ComponentRemoved(_, type_id) => {
+                *type_id == TypeId::of::<T>()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::resources::events::{Events, PollConfiguration};
+
+    use super::*;
+
+    #[derive(Clone, PartialEq)]
+    struct Health(u32);
+
+    struct NotComparable;
+
+    #[test]
+    fn change_tracker_test() {
+        let mut data = GameData::default();
+        let first = data.push((Health(3), NotComparable));
+        let second = data.push((Health(5),));
+        let mut tracker = ChangeTracker::<Health>::new();
+        let mut presence_tracker = ChangeTracker::<NotComparable>::new();
+
+        tracker.update(&data);
+        presence_tracker.update_presence(&data);
+        assert_eq!(2, tracker.query::<Added<Health>>().len());
+        assert_eq!(&[first], presence_tracker.query::<Added<NotComparable>>());
+
+        data.entry_mut::<&mut Health>(first).unwrap().0 = 2;
+        data.entry_mut::<&mut Health>(second).unwrap().0 = 5;
+        let third = data.push((Health(1),));
+        let _r = data.remove(second);
+        let _r = data.remove_component::<NotComparable>(first);
+        tracker.update(&data);
+        presence_tracker.update_presence(&data);
+        assert_eq!(&[third], tracker.query::<Added<Health>>());
+        assert_eq!(&[first], tracker.query::<Changed<Health>>());
+        assert_eq!(&[second], tracker.query::<Removed<Health>>());
+        assert_eq!(&[first], presence_tracker.query::<Removed<NotComparable>>());
+
+        tracker.update(&data);
+        assert!(tracker.added().is_empty() && tracker.changed().is_empty() && tracker.removed().is_empty());
+    }
+
+    #[test]
+    fn lifecycle_events_test() {
+        let mut data = GameData::default();
+        data.insert_resource(Events::default());
+        let subscriber = data.events().channel::<LifecycleEvent>().subscribe(PollConfiguration::default());
+        let ignored = data.push((Health(1),));
+        data.flush_lifecycle_events();
+
+        data.subworld.enable_lifecycle_events();
+        let entity = data.push((Health(3),));
+        let _r = data.add_components(entity, (NotComparable, Health(2)));
+        let _r = data.remove_component::<Health>(entity);
+        let _r = data.remove(entity);
+        let _r = data.remove_component::<Health>(ignored);
+        data.flush_lifecycle_events();
+
+        let mut events: Vec<LifecycleEvent> = Vec::new();
+        while let Some(polled) = data.events().channel::<LifecycleEvent>().poll(&subscriber).ok().filter(|p| !p.is_empty()) {
+            events.extend(polled);
+        }
+        assert_eq!(
+            vec![
+                LifecycleEvent::EntitySpawned(entity),
+                LifecycleEvent::ComponentAdded(entity, TypeId::of::<Health>()),
+                LifecycleEvent::ComponentAdded(entity, TypeId::of::<NotComparable>()),
+                LifecycleEvent::ComponentRemoved(entity, TypeId::of::<Health>()),
+                LifecycleEvent::ComponentRemoved(entity, TypeId::of::<NotComparable>()),
+                LifecycleEvent::EntityDespawned(entity),
+                LifecycleEvent::ComponentRemoved(ignored, TypeId::of::<Health>()),
+            ],
+            events
+        );
+        assert!(events[1].concerns::<Health>());
+    }
+}
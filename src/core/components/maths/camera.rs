@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 pub(crate) struct DefaultCamera;
 
 /// Mandatory component to add to the World to have anything rendered.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Camera {
     pub(crate) left: f32,
     pub(crate) right: f32,
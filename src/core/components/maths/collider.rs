@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use geo_clipper::Clipper;
 use geo_types::{Coord, LineString};
 use hecs::Entity;
@@ -6,7 +7,7 @@ use crate::core::components::maths::{coordinates::Coordinates, Pivot, transform:
 use crate::utils::maths::{centroid_polygon, rotate_point_around_pivot, Vector};
 
 /// `ColliderMask` will serve as a 'mask' to allow filter while collisions happen
-#[derive(PartialEq, Clone, Eq, Hash, Debug)]
+#[derive(PartialEq, Clone, Eq, Hash, Debug, Serialize, Deserialize)]
 pub enum ColliderMask {
     None,
     Character,
@@ -18,7 +19,7 @@ pub enum ColliderMask {
 }
 
 /// `ColliderType` will determine the shape of the collider.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ColliderType {
     SquareCollider(usize),
     RectangleCollider(usize, usize),
@@ -26,15 +27,21 @@ pub enum ColliderType {
 }
 
 /// The main collider representation to add to an entity, using the new function
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Collider {
     collider_mask: ColliderMask,
     collider_type: ColliderType,
+    #[serde(default)]
     collision_filter: Vec<ColliderMask>,
+    #[serde(skip)]
     collisions: Vec<Collision>,
+    #[serde(default)]
     offset: Vector,
+    #[serde(default)]
     debug_lines: bool,
+    #[serde(default)]
     local_pivot: Option<Pivot>,
+    #[serde(default)]
     parent_pivot: Option<Pivot>,
 }
 
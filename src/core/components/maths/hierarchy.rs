@@ -1,11 +1,26 @@
 use hecs::Entity;
+use serde::{Deserialize, Serialize};
+
+use crate::core::snapshot::{EntityMap, MapEntities};
 
 /// A component creating a parent link to the wrapped entity
-#[derive(Debug)]
-pub struct Parent(pub Entity);
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Parent(#[serde(with = "crate::core::snapshot::entity_serde")] pub Entity);
+
+impl MapEntities for Parent {
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        self.0 = entity_map.get(self.0);
+    }
+}
 
 /// A component creating a link to the wrapped entities
 /// This component will be automatically added to an entity by Scion
 /// if a component references this entity with a [`Parent`] component
-#[derive(Debug)]
-pub struct Children(pub Vec<Entity>);
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Children(#[serde(with = "crate::core::snapshot::entities_serde")] pub Vec<Entity>);
+
+impl MapEntities for Children {
+    fn map_entities(&mut self, entity_map: &EntityMap) {
+        self.0.iter_mut().for_each(|entity| *entity = entity_map.get(*entity));
+    }
+}
@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 pub mod camera;
 pub mod collider;
 pub mod coordinates;
@@ -6,7 +8,7 @@ pub mod transform;
 pub mod padding;
 
 /// `Pivot` tells where the pivot point of a component is
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum Pivot {
     /// Pivot is on the top left corner of the shape
     TopLeft,
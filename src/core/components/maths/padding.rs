@@ -1,4 +1,6 @@
-#[derive(Default, Copy, Clone)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Default, Copy, Clone, Serialize, Deserialize)]
 pub struct Padding {
     pub(crate) top: Option<f32>,
     pub(crate) left: Option<f32>,
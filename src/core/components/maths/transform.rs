@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{core::components::maths::coordinates::Coordinates, utils::maths::Vector};
 
 /// represents the bounds for a Transoform with min and max values
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
 struct Bounds {
     pub(crate) min_x: Option<f32>,
     pub(crate) max_x: Option<f32>,
@@ -11,7 +13,10 @@ struct Bounds {
 
 /// Component used by the renderer to know where and how to represent an object.
 /// Default is position 0;0 with a scale of 1.0 and no angle.
-#[derive(Debug, Copy, Clone)]
+/// The global values are computed by the engine, so a `Transform` is deserialized from its local values only,
+/// all of them being optional.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "LocalTransform")]
 pub struct Transform {
     pub(crate) local_translation: Coordinates,
     pub(crate) global_translation: Coordinates,
@@ -40,6 +45,32 @@ impl Default for Transform {
     }
 }
 
+/// Deserialized values of a [`Transform`]
+#[derive(Deserialize)]
+#[serde(default)]
+struct LocalTransform {
+    local_translation: Coordinates,
+    scale: f32,
+    local_angle: f32,
+    use_screen_as_origin: bool,
+    bounds: Bounds,
+}
+
+impl Default for LocalTransform {
+    fn default() -> Self {
+        Self { local_translation: Default::default(), scale: 1.0, local_angle: 0.0, use_screen_as_origin: false, bounds: Default::default() }
+    }
+}
+
+impl From<LocalTransform> for Transform {
+    fn from(local: LocalTransform) -> Self {
+        let mut transform = Transform::new(local.local_translation, local.scale, local.local_angle);
+        transform.use_screen_as_origin = local.use_screen_as_origin;
+        transform.bounds = local.bounds;
+        transform
+    }
+}
+
 impl Transform {
     /// Creates a new transform using provided values.
     pub fn new(translation: Coordinates, scale: f32, angle: f32) -> Self {
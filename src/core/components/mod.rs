@@ -1 +1,6 @@
-pub mod maths;
\ No newline at end of file
+use serde::{Deserialize, Serialize};
+
+pub mod maths;
+/// Struct to add to any entity to keep it alive when the scene that created it stops
+#[derive(Serialize, Deserialize)]
+pub struct Persistent;
@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use crate::core::resources::inputs::mouse::MouseEvent;
+use crate::core::resources::inputs::types::KeyboardEvent;
+use crate::core::resources::time::Time;
+use crate::core::scion_runner::ScionRunner;
+use crate::core::world::GameData;
+use crate::utils::frame_limiter::{FrameLimiter, FrameLimiterConfig};
+
+/// `HeadlessRunner` runs a Scion application without window, rendering nor real time.
+/// Frames are only executed when asked, using a virtual clock, which makes it suited to drive
+/// scenes and systems from automated tests.
+/// This runner is returned when calling [`crate::ScionBuilder::build_headless`].
+/// ```no_run
+/// use std::time::Duration;
+/// use scion::config::scion_config::ScionConfig;
+/// use scion::core::world::World;
+/// use scion::ScionBuilder;
+///
+/// let mut runner = ScionBuilder::new(ScionConfig::default()).build_headless();
+/// runner.step_frames(10);
+/// runner.step_for(Duration::from_secs(2));
+/// assert!(runner.game_data().entities().is_empty());
+/// runner.stop();
+/// ```
+pub struct HeadlessRunner {
+    runner: ScionRunner,
+    frame_limiter: FrameLimiter,
+    frame_duration: Duration,
+}
+
+impl HeadlessRunner {
+    pub(crate) fn new(mut runner: ScionRunner) -> Self {
+        runner.setup();
+        let fixed_delta_duration = runner
+            .game_data
+            .get_resource::<Time>()
+            .expect("Time is an internal resource and can't be missing")
+            .fixed_delta_duration();
+        Self {
+            runner,
+            frame_limiter: FrameLimiter::new(FrameLimiterConfig::default(), fixed_delta_duration),
+            frame_duration: Duration::from_secs(1) / 60,
+        }
+    }
+
+    /// Sets the virtual duration of a frame executed by [`HeadlessRunner::step`]. Default is 1/60s.
+    pub fn with_frame_duration(mut self, frame_duration: Duration) -> Self {
+        assert!(!frame_duration.is_zero(), "The frame duration of the headless runner can't be zero");
+        self.frame_duration = frame_duration;
+        self
+    }
+
+    /// Whether or not the game is still running, i.e. no exit was requested using [`crate::core::resources::window::Window::exit`].
+    /// Steps are ignored once the game is not running anymore.
+    pub fn is_running(&self) -> bool {
+        self.runner.running
+    }
+
+    /// Simulates a click on the window close button, visible by the scene and systems during the next step
+    pub fn request_close(&mut self) {
+        self.runner.game_data.window().request_close();
+    }
+
+    /// Executes a single frame, followed by the fixed updates falling in this frame
+    pub fn step(&mut self) {
+        self.step_duration(self.frame_duration);
+    }
+
+    /// Executes `frames` frames
+    pub fn step_frames(&mut self, frames: usize) {
+        (0..frames).for_each(|_| self.step());
+    }
+
+    /// Executes as many frames as needed to make `duration` elapse. The last frame is shortened
+    /// when `duration` is not a multiple of the frame duration.
+    pub fn step_for(&mut self, duration: Duration) {
+        let mut remaining = duration;
+        while remaining >= self.frame_duration {
+            self.step();
+            remaining -= self.frame_duration;
+        }
+        if !remaining.is_zero() {
+            self.step_duration(remaining);
+        }
+    }
+
+    /// Access to the game data between two steps
+    pub fn game_data(&self) -> &GameData {
+        &self.runner.game_data
+    }
+
+    /// Mutable access to the game data between two steps
+    pub fn game_data_mut(&mut self) -> &mut GameData {
+        &mut self.runner.game_data
+    }
+
+    /// Simulates a keyboard event, visible by the scene and systems during the next step
+    pub fn inject_keyboard_event(&mut self, event: KeyboardEvent) {
+        self.runner.game_data.inputs().add_keyboard_event(event);
+    }
+
+    /// Simulates a mouse click event, visible by the scene and systems during the next step
+    pub fn inject_mouse_event(&mut self, event: MouseEvent) {
+        self.runner.game_data.inputs().add_click_event(event);
+    }
+
+    /// Simulates a move of the mouse cursor
+    pub fn set_mouse_position(&mut self, x: f64, y: f64) {
+        self.runner.game_data.inputs().set_mouse_position(x, y);
+    }
+
+    /// Stops the application, calling the current scene `on_stop` and the systems shutdown hooks.
+    /// Returns the game data, to assert on the stopped game.
+    pub fn stop(mut self) -> GameData {
+        self.runner.shutdown();
+        self.runner.game_data
+    }
+
+    fn step_duration(&mut self, frame_duration: Duration) {
+        if !self.runner.running {
+            return;
+        }
+        let scaled_duration = self.runner.advance_time(frame_duration);
+        self.runner.update(scaled_duration);
+        for _ in 0..self.frame_limiter.accumulate_fixed_steps(scaled_duration) {
+            self.runner.fixed_update();
+        }
+        self.runner.end_frame();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::scion_config::ScionConfig;
+    use crate::core::resources::inputs::types::{InputState, KeyCode};
+    use crate::core::scene::Scene;
+    use crate::core::world::World;
+    use crate::ScionBuilder;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct CountingScene;
+
+    impl Scene for CountingScene {
+        fn on_update(&mut self, data: &mut GameData) {
+            data.push((1_u8,));
+            data.inputs().on_key_pressed(KeyCode::Space, || {
+                data.game_state_mut().set_bool("space", true);
+            });
+        }
+
+        fn on_fixed_update(&mut self, data: &mut GameData) {
+            data.push((1_u16,));
+        }
+
+        fn on_stop(&mut self, data: &mut GameData) {
+            data.game_state_mut().set_bool("stopped", true);
+        }
+    }
+
+    fn runner() -> HeadlessRunner {
+        ScionBuilder::new(ScionConfig::default())
+            .with_scene::<CountingScene>()
+            .with_system(|data: &mut GameData| {
+                data.push((1_u32,));
+            })
+            .build_headless()
+    }
+
+    fn count<T: hecs::Component>(runner: &HeadlessRunner) -> usize {
+        runner.game_data().query::<&T>().iter().count()
+    }
+
+    #[test]
+    fn step_frames_test() {
+        let mut runner = runner();
+        runner.step_frames(3);
+        assert_eq!(3, count::<u8>(&runner));
+        assert_eq!(3, count::<u32>(&runner));
+        assert_eq!(3, count::<u16>(&runner));
+        let time = runner.game_data().get_resource::<Time>().unwrap();
+        assert_eq!(3, time.frame_number());
+        assert_eq!(Duration::from_secs(1) / 60 * 3, time.total_duration());
+    }
+
+    #[test]
+    fn step_for_test() {
+        let mut runner = runner().with_frame_duration(Duration::from_millis(40));
+        runner.step_for(Duration::from_millis(100));
+        assert_eq!(3, count::<u8>(&runner));
+        assert_eq!(6, count::<u16>(&runner));
+        let time = runner.game_data().get_resource::<Time>().unwrap();
+        assert_eq!(Duration::from_millis(20), time.delta_duration());
+        assert_eq!(Duration::from_millis(100), time.total_duration());
+    }
+
+    #[test]
+    fn time_scale_test() {
+        let mut runner = runner().with_frame_duration(Duration::from_millis(50));
+        runner.game_data().get_resource_mut::<Time>().unwrap().set_time_scale(0.5);
+        runner.step_for(Duration::from_millis(200));
+        assert_eq!(6, count::<u16>(&runner));
+        runner.game_data().get_resource_mut::<Time>().unwrap().pause();
+        runner.step_for(Duration::from_millis(200));
+        assert_eq!(6, count::<u16>(&runner));
+        assert_eq!(8, count::<u8>(&runner));
+        let time = runner.game_data().get_resource::<Time>().unwrap();
+        assert_eq!(Duration::from_millis(100), time.total_duration());
+        assert_eq!(Duration::from_millis(50), time.unscaled_delta_duration());
+    }
+
+    #[test]
+    fn injected_inputs_test() {
+        let mut runner = runner();
+        runner.step();
+        assert!(!runner.game_data().game_state().get_bool("space"));
+        runner.inject_keyboard_event(KeyboardEvent { keycode: KeyCode::Space, state: InputState::Pressed });
+        runner.step();
+        assert!(runner.game_data().game_state().get_bool("space"));
+    }
+
+    #[test]
+    fn exit_test() {
+        let mut runner = runner();
+        runner.game_data().window().intercept_close(true);
+        runner.request_close();
+        runner.step();
+        assert!(runner.is_running());
+
+        runner.game_data().window().exit();
+        runner.step();
+        assert!(!runner.is_running());
+        runner.step();
+        assert_eq!(2, count::<u8>(&runner));
+        assert!(!runner.game_data().game_state().get_bool("stopped"));
+
+        assert!(runner.stop().game_state().get_bool("stopped"));
+    }
+}
@@ -1,11 +1,15 @@
 pub(crate) mod audio_controller;
+pub mod change_detection;
 pub mod package;
+pub mod prefab;
 pub mod resources;
 pub mod scene;
-pub(crate) mod scheduler;
+pub mod scheduler;
+pub mod snapshot;
 pub mod state;
 pub mod systems;
 pub mod world;
 pub mod application_builder;
 pub mod scion_runner;
+pub mod headless_runner;
 pub mod components;
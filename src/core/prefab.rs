@@ -0,0 +1,354 @@
+//! Prefabs describe an entity, its components and its children in a json file, to spawn it from data.
+
+use std::path::Path;
+use std::time::SystemTime;
+
+use hecs::Entity;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::core::components::maths::hierarchy::Parent;
+use crate::core::resources::asset_manager::AssetRef;
+use crate::core::snapshot::{ComponentRegistry, SnapshotError};
+use crate::core::world::{GameData, World};
+use crate::utils::file::{read_file, read_file_modification_time};
+
+/// `PrefabError` represents the different errors that loading or spawning a prefab can return
+#[derive(Debug)]
+pub enum PrefabError {
+    /// The prefab file can't be read. Contains the io error message
+    IoError(String),
+    /// The prefab, its overrides or one of its components can't be deserialized. Contains the serialization error message
+    SerializationError(String),
+    /// The prefab uses a component that is not registered into the `ComponentRegistry`
+    UnregisteredComponent(String),
+    /// The prefab ref does not match any prefab of the `AssetManager`
+    MissingPrefab,
+}
+
+/// `Prefab` is the definition of an entity, read from a json file where components are listed by
+/// the name they are registered with into the [`ComponentRegistry`]:
+/// ```json
+/// {
+///   "components": {
+///     "Transform": { "local_translation": { "x": 10.0, "y": 20.0, "z": 1 } },
+///     "Material": { "Texture": "assets/ship.png" }
+///   },
+///   "children": [
+///     { "components": { "Transform": { "local_translation": { "x": 5.0, "y": 0.0 } } } }
+///   ]
+/// }
+/// ```
+/// Prefabs are registered into the `AssetManager`, then spawned with [`Prefab::spawn`]. With the `hot-reload`
+/// feature, modified prefab files are reloaded and their spawned entities are updated.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Prefab {
+    #[serde(default)]
+    components: Map<String, Value>,
+    #[serde(default)]
+    children: Vec<Prefab>,
+}
+
+/// Component added by the engine to the root entity of a spawned prefab
+#[derive(Debug)]
+pub struct PrefabInstance {
+    prefab: AssetRef<Prefab>,
+    overrides: Map<String, Value>,
+    /// Names of the components inserted into the root entity from the prefab and its overrides
+    components: Vec<String>,
+    /// Every entity spawned for the children of the prefab
+    spawned_children: Vec<Entity>,
+}
+
+impl PrefabInstance {
+    /// Prefab this entity has been spawned from
+    pub fn prefab(&self) -> AssetRef<Prefab> {
+        self.prefab
+    }
+}
+
+/// A prefab stored into the `AssetManager`, with what's needed to reload it
+pub(crate) struct PrefabAsset {
+    pub(crate) path: String,
+    pub(crate) prefab: Prefab,
+    pub(crate) modification_time: Option<SystemTime>,
+}
+
+impl PrefabAsset {
+    pub(crate) fn load(path: &str) -> Result<PrefabAsset, PrefabError> {
+        let modification_time = read_file_modification_time(Path::new(path)).ok();
+        let bytes = read_file(Path::new(path)).map_err(|e| PrefabError::IoError(format!("{:?}", e)))?;
+        let prefab = serde_json::from_slice(&bytes).map_err(serialization_error)?;
+        Ok(PrefabAsset { path: path.to_string(), prefab, modification_time })
+    }
+
+    /// Reloads the prefab if its file has been modified since it was loaded. Returns whether or not it was reloaded
+    pub(crate) fn reload_if_modified(&mut self) -> bool {
+        let modification_time = read_file_modification_time(Path::new(&self.path)).ok();
+        if modification_time.is_none() || modification_time == self.modification_time {
+            return false;
+        }
+        match PrefabAsset::load(&self.path) {
+            Ok(asset) => {
+                info!("Prefab '{}' reloaded", self.path);
+                *self = asset;
+                true
+            }
+            Err(e) => {
+                error!("Impossible to reload prefab '{}': {:?}", self.path, e);
+                self.modification_time = modification_time;
+                false
+            }
+        }
+    }
+}
+
+impl Prefab {
+    /// Parses a prefab from its json description, the content of a prefab file
+    pub fn from_json(json: &str) -> Result<Prefab, PrefabError> {
+        serde_json::from_str(json).map_err(serialization_error)
+    }
+
+    /// Spawns the entity described by `prefab` and its children, and returns the root entity
+    pub fn spawn(data: &mut GameData, prefab: &AssetRef<Prefab>) -> Result<Entity, PrefabError> {
+        Prefab::spawn_with_overrides(data, prefab, Value::Object(Map::new()))
+    }
+
+    /// Spawns the entity described by `prefab` and its children, replacing values of the root entity components
+    /// by the ones of `overrides`. Overrides are merged into the components: only the given fields are replaced.
+    /// ```no_run
+    /// use serde_json::json;
+    /// use scion::core::prefab::Prefab;
+    /// use scion::core::world::GameData;
+    ///
+    /// fn spawn_ship(data: &mut GameData) {
+    ///     let ship = data.assets_mut().register_prefab("assets/ship.json").unwrap();
+    ///     let overrides = json!({ "Transform": { "local_translation": { "x": 50.0, "y": 120.0 } } });
+    ///     Prefab::spawn_with_overrides(data, &ship, overrides).unwrap();
+    /// }
+    /// ```
+    pub fn spawn_with_overrides(data: &mut GameData, prefab: &AssetRef<Prefab>, overrides: Value) -> Result<Entity, PrefabError> {
+        let Value::Object(overrides) = overrides else {
+            return Err(PrefabError::SerializationError("Prefab overrides must be a json object".to_string()));
+        };
+        let definition = data.assets().retrieve_prefab(prefab).cloned().ok_or(PrefabError::MissingPrefab)?;
+        let registry = data.component_registry().clone();
+        let entity = data.push(());
+        let mut spawned_children = Vec::new();
+        let spawned = definition
+            .insert_components(data, &registry, entity, &overrides)
+            .and_then(|_| definition.spawn_children(data, &registry, entity, &mut spawned_children));
+        if let Err(e) = spawned {
+            spawned_children.into_iter().chain(std::iter::once(entity)).for_each(|entity| {
+                let _r = data.remove(entity);
+            });
+            return Err(e);
+        }
+        let components = definition.component_names(&overrides);
+        let instance = PrefabInstance { prefab: *prefab, overrides, components, spawned_children };
+        let _r = data.add_components(entity, (instance,));
+        Ok(entity)
+    }
+
+    /// Inserts the components of the prefab, merged with `overrides`, into `entity`
+    fn insert_components(
+        &self,
+        data: &mut GameData,
+        registry: &ComponentRegistry,
+        entity: Entity,
+        overrides: &Map<String, Value>,
+    ) -> Result<(), PrefabError> {
+        let mut components = self.components.clone();
+        merge(&mut components, overrides);
+        for (name, value) in components {
+            registry.insert_component(data, entity, &name, value).map_err(snapshot_error)?;
+        }
+        Ok(())
+    }
+
+    /// Names of the components of the prefab merged with `overrides`
+    fn component_names(&self, overrides: &Map<String, Value>) -> Vec<String> {
+        let mut names: Vec<String> = self.components.keys().chain(overrides.keys()).cloned().collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    fn spawn_children(
+        &self,
+        data: &mut GameData,
+        registry: &ComponentRegistry,
+        parent: Entity,
+        spawned: &mut Vec<Entity>,
+    ) -> Result<(), PrefabError> {
+        for child in self.children.iter() {
+            let entity = data.push((Parent(parent),));
+            spawned.push(entity);
+            child.insert_components(data, registry, entity, &Map::new())?;
+            child.spawn_children(data, registry, entity, spawned)?;
+        }
+        Ok(())
+    }
+}
+
+/// Updates the entities spawned from the prefabs of `reloaded`: the components of the root entities are replaced,
+/// the ones that are no longer part of the prefab nor of the overrides are removed, and their children are spawned again.
+pub(crate) fn update_prefab_instances(data: &mut GameData, reloaded: &[AssetRef<Prefab>]) {
+    let instances: Vec<(Entity, PrefabInstance)> = data
+        .query_mut::<&mut PrefabInstance>()
+        .into_iter()
+        .filter(|(_, instance)| reloaded.iter().any(|prefab| prefab.0 == instance.prefab.0))
+        .map(|(entity, instance)| {
+            let previous = PrefabInstance {
+                prefab: instance.prefab,
+                overrides: instance.overrides.clone(),
+                components: std::mem::take(&mut instance.components),
+                spawned_children: std::mem::take(&mut instance.spawned_children),
+            };
+            (entity, previous)
+        })
+        .collect();
+    let registry = data.component_registry().clone();
+    for (entity, previous) in instances {
+        previous.spawned_children.into_iter().for_each(|child| {
+            let _r = data.remove(child);
+        });
+        let Some(definition) = data.assets().retrieve_prefab(&previous.prefab).cloned() else {
+            continue;
+        };
+        let components = definition.component_names(&previous.overrides);
+        previous.components.iter().filter(|name| !components.contains(name)).for_each(|name| {
+            let _r = registry.remove_component(data, entity, name);
+        });
+        let mut spawned_children = Vec::new();
+        let updated = definition
+            .insert_components(data, &registry, entity, &previous.overrides)
+            .and_then(|_| definition.spawn_children(data, &registry, entity, &mut spawned_children));
+        if let Err(e) = updated {
+            error!("Impossible to update entity {:?} with its reloaded prefab: {:?}", entity, e);
+        }
+        if let Ok(instance) = data.entry_mut::<&mut PrefabInstance>(entity) {
+            instance.components = components;
+            instance.spawned_children = spawned_children;
+        }
+    }
+}
+
+/// Merges `overrides` into `fields`: objects are merged field by field, other values are replaced
+fn merge(fields: &mut Map<String, Value>, overrides: &Map<String, Value>) {
+    for (key, override_value) in overrides {
+        match (fields.get_mut(key), override_value) {
+            (Some(Value::Object(field)), Value::Object(override_fields)) => merge(field, override_fields),
+            _ => {
+                fields.insert(key.to_string(), override_value.clone());
+            }
+        }
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> PrefabError {
+    PrefabError::SerializationError(e.to_string())
+}
+
+fn snapshot_error(e: SnapshotError) -> PrefabError {
+    match e {
+        SnapshotError::SerializationError(message) => PrefabError::SerializationError(message),
+        SnapshotError::UnregisteredComponent(name) => PrefabError::UnregisteredComponent(name),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::components::maths::hierarchy::Children;
+    use crate::core::components::maths::transform::Transform;
+    use crate::core::resources::asset_manager::AssetManager;
+    use crate::core::systems::hierarchy_system::children_manager_system;
+    use crate::graphics::components::material::Material;
+
+    use super::*;
+
+    const SHIP: &str = r#"{
+        "components": {
+            "Transform": { "local_translation": { "x": 10.0, "y": 20.0, "z": 1 } },
+            "Material": { "Texture": "ship.png" }
+        },
+        "children": [
+            { "components": { "Transform": { "scale": 2.0 } }, "children": [ { "components": { "Hide": null } } ] }
+        ]
+    }"#;
+
+    fn game_data() -> GameData {
+        let mut data = GameData::default();
+        data.insert_resource(AssetManager::default());
+        data.insert_resource(ComponentRegistry::with_engine_components());
+        data
+    }
+
+    fn translation(data: &GameData, entity: Entity) -> (f32, f32) {
+        let transform = *data.entry::<&Transform>(entity).unwrap().get().unwrap();
+        (transform.translation().x(), transform.translation().y())
+    }
+
+    #[test]
+    fn spawn_prefab_with_overrides_test() {
+        let mut data = game_data();
+        let ship = data.assets_mut().add_prefab("ship.json", Prefab::from_json(SHIP).unwrap());
+
+        let first = Prefab::spawn(&mut data, &ship).unwrap();
+        let overrides = serde_json::json!({ "Transform": { "local_translation": { "y": 50.0 } } });
+        let second = Prefab::spawn_with_overrides(&mut data, &ship, overrides).unwrap();
+        children_manager_system(&mut data);
+
+        assert_eq!((10., 20.), translation(&data, first));
+        assert_eq!((10., 50.), translation(&data, second));
+        assert!(matches!(data.entry::<&Material>(second).unwrap().get().unwrap().clone(), Material::Texture(path) if path == "ship.png"));
+        let child = data.entry::<&Children>(first).unwrap().get().unwrap().0[0];
+        assert_eq!(2., data.entry::<&Transform>(child).unwrap().get().unwrap().scale);
+        assert_eq!(1, data.entry::<&Children>(child).unwrap().get().unwrap().0.len());
+        assert_eq!(6, data.entities().len());
+        assert_eq!(ship.0, data.entry::<&PrefabInstance>(first).unwrap().get().unwrap().prefab().0);
+    }
+
+    #[test]
+    fn invalid_prefab_is_not_spawned_test() {
+        let mut data = game_data();
+        let prefab = r#"{ "components": { "Transform": {} }, "children": [ { "components": { "Unknown": 1 } } ] }"#;
+        let invalid = data.assets_mut().add_prefab("invalid.json", Prefab::from_json(prefab).unwrap());
+
+        assert!(matches!(Prefab::spawn(&mut data, &invalid), Err(PrefabError::UnregisteredComponent(name)) if name == "Unknown"));
+        assert!(matches!(
+            Prefab::spawn_with_overrides(&mut data, &invalid, Value::Bool(true)),
+            Err(PrefabError::SerializationError(_))
+        ));
+        assert_eq!(0, data.entities().len());
+    }
+
+    #[test]
+    fn prefab_instances_are_updated_on_reload_test() {
+        let dir = std::env::temp_dir().join(format!("scion_prefab_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ship.json");
+        std::fs::write(&path, SHIP).unwrap();
+
+        let mut data = game_data();
+        let ship = data.assets_mut().register_prefab(path.to_str().unwrap()).unwrap();
+        let overrides = serde_json::json!({ "Transform": { "local_translation": { "y": 50.0 } } });
+        let entity = Prefab::spawn_with_overrides(&mut data, &ship, overrides).unwrap();
+
+        assert!(data.assets_mut().reload_modified_prefabs().is_empty());
+        let reloaded = r#"{ "components": { "Transform": { "local_translation": { "x": 30.0, "y": 0.0 } } } }"#;
+        std::fs::write(&path, reloaded).unwrap();
+        // Moves the modification time forward, as the rewrite may happen within the file system time resolution
+        let modified = std::time::SystemTime::now() + std::time::Duration::from_secs(10);
+        std::fs::File::options().write(true).open(&path).unwrap().set_modified(modified).unwrap();
+        let reloaded = data.assets_mut().reload_modified_prefabs();
+        assert_eq!(1, reloaded.len());
+        update_prefab_instances(&mut data, &reloaded);
+
+        assert_eq!((30., 50.), translation(&data, entity));
+        assert!(data.entry::<&Material>(entity).unwrap().get().is_none());
+        assert_eq!(1, data.entities().len());
+        let _r = std::fs::remove_dir_all(dir);
+    }
+}
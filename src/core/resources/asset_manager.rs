@@ -1,8 +1,13 @@
 use std::{collections::HashMap, marker::PhantomData};
 
+use image::DynamicImage;
 use log::debug;
+use serde::{Deserialize, Serialize};
 
+use crate::core::prefab::{Prefab, PrefabAsset, PrefabError};
+use crate::core::scene::SceneId;
 use crate::graphics::components::{material::Material, tiles::tileset::Tileset};
+use crate::graphics::components::tiles::atlas::data::TilemapAtlas;
 use crate::graphics::components::ui::font::Font;
 
 /// `AssetManager` is resource that will link assets to an asset ref to allow reusability of assets
@@ -16,6 +21,21 @@ pub struct AssetManager {
     asset_ref_registry: HashMap<AssetType, usize>,
     materials: HashMap<usize, Material>,
     fonts: HashMap<usize, Font>,
+    /// Tilemaps preloaded by the scenes, waiting to be loaded into the world. Key is the tilemap name
+    preloaded_tilemaps: HashMap<String, Preloaded<TilemapAtlas>>,
+    /// Images preloaded by the scenes, waiting to be sent to the renderer. Key is the image path
+    preloaded_images: HashMap<String, Preloaded<DynamicImage>>,
+    /// A registry to keep track of the prefabs already loaded. Key is the prefab path
+    prefab_registry: HashMap<String, usize>,
+    prefabs: HashMap<usize, PrefabAsset>,
+    /// Scene owning the assets preloaded from now
+    pub(crate) scene_owner: Option<SceneId>,
+}
+
+/// An asset preloaded by a scene, dropped with its scene if it's never used
+struct Preloaded<T> {
+    asset: T,
+    owner: Option<SceneId>,
 }
 
 impl AssetManager {
@@ -40,6 +60,25 @@ impl AssetManager {
             .to_string()
     }
 
+    pub(crate) fn find_atlas_path(&self, asset_type: &AssetType) -> Option<String> {
+        self.atlas_path_registry.get(asset_type).cloned()
+    }
+
+    /// Names of the tilesets having a registered atlas path
+    pub(crate) fn registered_tileset_names(&self) -> Vec<String> {
+        self.atlas_path_registry
+            .keys()
+            .filter_map(|asset_type| match asset_type {
+                AssetType::Tileset(name) => Some(name.to_string()),
+                AssetType::Tilemap(_) => None,
+            })
+            .collect()
+    }
+
+    pub(crate) fn find_texture_for_tileset(&self, tileset_name: &str) -> Option<String> {
+        self.texture_path_registry.get(tileset_name).cloned()
+    }
+
     pub(crate) fn get_texture_for_tileset(&self, tileset_name: &str) -> String {
         self.texture_path_registry
             .get(tileset_name)
@@ -102,12 +141,71 @@ impl AssetManager {
         self.asset_ref_registry.get(&AssetType::Tileset(tileset_name.to_string())).map(|id| AssetRef(*id, PhantomData))
     }
 
+    pub(crate) fn add_preloaded_tilemap(&mut self, name: String, tilemap: TilemapAtlas) {
+        self.preloaded_tilemaps.insert(name, Preloaded { asset: tilemap, owner: self.scene_owner });
+    }
+
+    /// Takes the tilemap preloaded for `name`, if any. The tilemap is only preloaded for its first loading.
+    pub(crate) fn take_preloaded_tilemap(&mut self, name: &str) -> Option<TilemapAtlas> {
+        self.preloaded_tilemaps.remove(name).map(|preloaded| preloaded.asset)
+    }
+
+    pub(crate) fn add_preloaded_image(&mut self, path: String, image: DynamicImage) {
+        self.preloaded_images.insert(path, Preloaded { asset: image, owner: self.scene_owner });
+    }
+
+    /// Takes the image preloaded for `path`, if any. The image is only preloaded for its first upload to the renderer.
+    pub(crate) fn take_preloaded_image(&mut self, path: &str) -> Option<DynamicImage> {
+        self.preloaded_images.remove(path).map(|preloaded| preloaded.asset)
+    }
+
+    /// Drops the tilemaps and images preloaded by `scene` that were never used
+    pub(crate) fn delete_scene_preloads(&mut self, scene: SceneId) {
+        self.preloaded_tilemaps.retain(|_, preloaded| preloaded.owner != Some(scene));
+        self.preloaded_images.retain(|_, preloaded| preloaded.owner != Some(scene));
+    }
+
     pub fn register_font(&mut self, font: Font) -> AssetRef<Font> {
         let next_ref = AssetRef(self.fonts.keys().count(), PhantomData);
         self.fonts.insert(next_ref.0, font);
         next_ref
     }
 
+    /// Loads the prefab file located at `path` and returns a reference to it.
+    /// If the prefab is already loaded, returns the existing asset ref instead
+    pub fn register_prefab(&mut self, path: &str) -> Result<AssetRef<Prefab>, PrefabError> {
+        if let Some(id) = self.prefab_registry.get(path) {
+            return Ok(AssetRef(*id, PhantomData));
+        }
+        debug!("Registering new prefab '{}' into the registry", path);
+        let asset = PrefabAsset::load(path)?;
+        Ok(self.insert_prefab_asset(asset))
+    }
+
+    /// Registers a prefab that is not loaded from a file, replacing the prefab registered with the same identifier
+    pub fn add_prefab(&mut self, identifier: &str, prefab: Prefab) -> AssetRef<Prefab> {
+        self.insert_prefab_asset(PrefabAsset { path: identifier.to_string(), prefab, modification_time: None })
+    }
+
+    pub fn retrieve_prefab(&self, asset_ref: &AssetRef<Prefab>) -> Option<&Prefab> {
+        self.prefabs.get(&asset_ref.0).map(|asset| &asset.prefab)
+    }
+
+    /// Reloads the prefabs whose file has been modified, and returns their refs
+    pub(crate) fn reload_modified_prefabs(&mut self) -> Vec<AssetRef<Prefab>> {
+        self.prefabs
+            .iter_mut()
+            .filter_map(|(id, asset)| if asset.reload_if_modified() { Some(AssetRef(*id, PhantomData)) } else { None })
+            .collect()
+    }
+
+    fn insert_prefab_asset(&mut self, asset: PrefabAsset) -> AssetRef<Prefab> {
+        let next_id = self.prefabs.keys().count();
+        let id = *self.prefab_registry.entry(asset.path.to_string()).or_insert(next_id);
+        self.prefabs.insert(id, asset);
+        AssetRef(id, PhantomData)
+    }
+
     pub fn retrieve_tileset(&self, asset_ref: &AssetRef<Material>) -> Option<&Tileset> {
         match self.materials.get(&asset_ref.0) {
             None => None,
@@ -119,11 +217,15 @@ impl AssetManager {
     }
 }
 
-#[derive(Clone, Eq, Copy, PartialEq, Hash)]
+/// `AssetRef` references an asset of the `AssetManager`. It is serialized as the index of the asset, only valid during
+/// the session : it can be captured into a `WorldSnapshot`, but components holding it are not written into the save slots.
+#[derive(Clone, Eq, Copy, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct AssetRef<T: Send + Sync>(pub(crate) usize, pub(crate) PhantomData<T>);
 
 impl Copy for AssetRef<Material>{}
 impl Copy for AssetRef<Font>{}
+impl Copy for AssetRef<Prefab>{}
 
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub enum AssetType {
@@ -1,4 +1,5 @@
 use std::sync::mpsc;
+use std::thread::JoinHandle;
 
 use rodio::{OutputStream, Sink};
 
@@ -9,14 +10,23 @@ use crate::core::audio_controller::AudioController;
 pub struct Audio {
     event_sender: mpsc::Sender<AudioEvent>,
     sounds_cursor: usize,
+    audio_thread: Option<JoinHandle<()>>,
 }
 
 impl Audio {
     pub(crate) fn default() -> Self {
         let (event_sender, receiver) = mpsc::channel();
 
-        std::thread::spawn(move || audio_controller::audio_thread(AudioController::new(receiver)));
-        Audio { event_sender, sounds_cursor: 0 }
+        let audio_thread = std::thread::spawn(move || audio_controller::audio_thread(AudioController::new(receiver)));
+        Audio { event_sender, sounds_cursor: 0, audio_thread: Some(audio_thread) }
+    }
+
+    /// Stops every sound and waits for the audio thread to end
+    pub(crate) fn shutdown(&mut self) {
+        let _r = self.event_sender.send(AudioEvent::Shutdown);
+        if let Some(audio_thread) = self.audio_thread.take() {
+            let _r = audio_thread.join();
+        }
     }
 
     /// Start to play the sound identified with `name`
@@ -59,4 +69,5 @@ impl Default for PlayConfig {
 pub(crate) enum AudioEvent {
     PlaySound { path: String, config: PlayConfig, sound_id: usize },
     StopSound { sound_id: usize },
+    Shutdown,
 }
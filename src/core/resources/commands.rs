@@ -0,0 +1,203 @@
+use std::collections::HashMap;
+
+use hecs::{Component, DynamicBundle, Entity};
+use log::warn;
+
+use crate::core::components::maths::hierarchy::Parent;
+use crate::core::world::{GameData, Resource, World};
+
+/// Handle on an entity spawned through [`Commands`], usable in the next commands until the end of the frame
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PendingEntity(usize);
+
+/// Target of a command, either an existing entity or one spawned by a previous command
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CommandTarget {
+    Entity(Entity),
+    Pending(PendingEntity),
+}
+
+impl From<Entity> for CommandTarget {
+    fn from(entity: Entity) -> Self {
+        CommandTarget::Entity(entity)
+    }
+}
+
+impl From<PendingEntity> for CommandTarget {
+    fn from(pending: PendingEntity) -> Self {
+        CommandTarget::Pending(pending)
+    }
+}
+
+type Command = Box<dyn FnOnce(&mut GameData, &mut HashMap<PendingEntity, Entity>) + Send + Sync>;
+
+/// `Commands` is a resource recording structural changes on the world (spawns, despawns, components
+/// and resources insertions) that can't be done while iterating a query. Recorded commands are
+/// applied in their recording order at the end of each scheduler stage. Commands targeting a [`PendingEntity`]
+/// recorded during a previous frame are dropped.
+#[derive(Default)]
+pub struct Commands {
+    queue: Vec<Command>,
+    next_pending: usize,
+    /// Entities spawned by the commands applied during the current frame
+    spawned: HashMap<PendingEntity, Entity>,
+}
+
+impl Commands {
+    /// Records the spawn of an entity with `components`
+    pub fn spawn(&mut self, components: impl DynamicBundle + Send + Sync + 'static) -> PendingEntity {
+        let pending = PendingEntity(self.next_pending);
+        self.next_pending += 1;
+        self.queue.push(Box::new(move |data, spawned| {
+            spawned.insert(pending, data.push(components));
+        }));
+        pending
+    }
+
+    /// Records the spawn of an entity with `components` and a [`Parent`] component referencing `parent`
+    pub fn spawn_child(&mut self, parent: impl Into<CommandTarget>, components: impl DynamicBundle + Send + Sync + 'static) -> PendingEntity {
+        let child = self.spawn(components);
+        let parent = parent.into();
+        self.queue.push(Box::new(move |data, spawned| {
+            if let (Some(parent), Some(child)) = (resolve(parent, spawned), resolve(child.into(), spawned)) {
+                let _r = data.add_components(child, (Parent(parent),));
+            }
+        }));
+        child
+    }
+
+    /// Records the insertion of `components` on `target`
+    pub fn insert(&mut self, target: impl Into<CommandTarget>, components: impl DynamicBundle + Send + Sync + 'static) {
+        let target = target.into();
+        self.queue.push(Box::new(move |data, spawned| {
+            if let Some(entity) = resolve(target, spawned) {
+                let _r = data.add_components(entity, components);
+            }
+        }));
+    }
+
+    /// Records the removal of the component `T` from `target`
+    pub fn remove<T: Component>(&mut self, target: impl Into<CommandTarget>) {
+        let target = target.into();
+        self.queue.push(Box::new(move |data, spawned| {
+            if let Some(entity) = resolve(target, spawned) {
+                let _r = data.remove_component::<T>(entity);
+            }
+        }));
+    }
+
+    /// Records the despawn of `target`
+    pub fn despawn(&mut self, target: impl Into<CommandTarget>) {
+        let target = target.into();
+        self.queue.push(Box::new(move |data, spawned| {
+            if let Some(entity) = resolve(target, spawned) {
+                let _r = data.remove(entity);
+            }
+        }));
+    }
+
+    /// Records the insertion of the resource `resource`, replacing any existing one of the same type
+    pub fn insert_resource<T: Resource>(&mut self, resource: T) {
+        self.queue.push(Box::new(move |data, _| data.insert_resource(resource)));
+    }
+
+    /// Returns the entity created for `pending`, once the commands that spawned it have been applied, until the end
+    /// of the frame
+    pub fn spawned(&self, pending: PendingEntity) -> Option<Entity> {
+        self.spawned.get(&pending).copied()
+    }
+
+    /// Returns whether or not some commands are waiting to be applied
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Forgets the entities spawned during the frame
+    pub(crate) fn end_frame(&mut self) {
+        self.spawned.clear();
+    }
+}
+
+fn resolve(target: CommandTarget, spawned: &HashMap<PendingEntity, Entity>) -> Option<Entity> {
+    match target {
+        CommandTarget::Entity(entity) => Some(entity),
+        CommandTarget::Pending(pending) => {
+            let entity = spawned.get(&pending).copied();
+            if entity.is_none() {
+                warn!("Command dropped as {:?} was not spawned during this frame", pending);
+            }
+            entity
+        }
+    }
+}
+
+/// Applies every recorded command on `data`
+pub(crate) fn apply_commands(data: &mut GameData) {
+    let queue = match data.get_resource_mut::<Commands>() {
+        Some(mut commands) if !commands.queue.is_empty() => std::mem::take(&mut commands.queue),
+        _ => return,
+    };
+    let mut spawned = std::mem::take(&mut data.commands().spawned);
+    queue.into_iter().for_each(|command| command(data, &mut spawned));
+    data.commands().spawned = spawned;
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::components::maths::transform::Transform;
+
+    use super::*;
+
+    #[test]
+    fn commands_are_applied_in_order_test() {
+        let mut data = GameData::default();
+        data.insert_resource(Commands::default());
+        let existing = data.push((Transform::default(),));
+
+        let (pending, child) = {
+            let mut commands = data.commands();
+            for (e, _) in data.query::<&Transform>().iter() {
+                commands.remove::<Transform>(e);
+                commands.insert(e, (1_usize,));
+            }
+            let pending = commands.spawn((Transform::default(),));
+            commands.insert(pending, (2_usize,));
+            let child = commands.spawn_child(pending, (3_usize,));
+            commands.despawn(child);
+            commands.insert_resource(42_u32);
+            (pending, commands.spawn_child(existing, (4_usize,)))
+        };
+        assert_eq!(1, data.query::<&Transform>().iter().count());
+
+        apply_commands(&mut data);
+
+        let spawned = data.commands().spawned(pending).unwrap();
+        assert!(data.entry::<&Transform>(existing).unwrap().get().is_none());
+        assert_eq!(1, *data.entry::<&usize>(existing).unwrap().get().unwrap());
+        assert_eq!(2, *data.entry::<&usize>(spawned).unwrap().get().unwrap());
+        let child = data.commands().spawned(child).unwrap();
+        assert_eq!(existing, data.entry::<&Parent>(child).unwrap().get().unwrap().0);
+        assert_eq!(3, data.entities().len());
+        assert_eq!(42, *data.get_resource::<u32>().unwrap());
+        assert!(data.commands().is_empty());
+    }
+
+    #[test]
+    fn pending_entities_are_resolved_until_the_end_of_the_frame_test() {
+        let mut data = GameData::default();
+        data.insert_resource(Commands::default());
+
+        let pending = data.commands().spawn((1_usize,));
+        apply_commands(&mut data);
+        data.commands().insert(pending, (Transform::default(),));
+        apply_commands(&mut data);
+        let spawned = data.commands().spawned(pending).unwrap();
+        assert!(data.entry::<&Transform>(spawned).unwrap().get().is_some());
+
+        data.commands().end_frame();
+        data.commands().insert(pending, (2_u8,));
+        apply_commands(&mut data);
+        assert!(data.commands().spawned(pending).is_none());
+        assert!(data.entry::<&u8>(spawned).unwrap().get().is_none());
+    }
+}
@@ -1,14 +1,19 @@
+use std::any::{type_name, TypeId};
 use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
+use std::time::Duration;
 
 use serde::{de::DeserializeOwned, ser};
 use serde_json::{from_str, to_string};
 
-use crate::core::resources::events::topic::{Topic, TopicConfiguration};
+use crate::core::resources::events::topic::{Topic, TopicConfiguration, TopicStorage};
+use crate::core::scene::SceneId;
 
 pub mod topic;
 
 pub type SubscriberId = usize;
 pub type Cursor = usize;
+pub type ScheduledEventId = usize;
 
 /// `PollConfiguration` represents the configuration of a subscriber when subscribing to a topic
 pub struct PollConfiguration {
@@ -28,13 +33,49 @@ pub enum EventError {
     TopicAlreadyExist,
     TopicDoesNotExist,
     SubscriberIdDoesNotExist,
+    ScheduledEventIdDoesNotExist,
+    /// The polled type does not match the events of the subscription. Contains the description of the mismatch
+    TypeMismatch(String),
 }
 
-/// `Events` is a convenience resource to help communicate between systems/resources/layers through events
+/// Topic a subscription reads: a named topic, or the typed channel of an event type
+#[derive(Clone, PartialEq, Eq, Hash)]
+enum TopicKey {
+    Named(String),
+    Channel(TypeId),
+}
+
+/// Publishes a scheduled event
+type Publisher = Box<dyn Fn(&mut Events) + Send + Sync>;
+
+/// An event published later by the engine, see [`Events::publish_after`] and [`Events::publish_every`]
+struct ScheduledEvent {
+    publisher: Publisher,
+    /// Time left before the next publication
+    remaining: Duration,
+    /// Time between two publications of a repeated event
+    period: Option<Duration>,
+    /// Scene that scheduled the event, and whose stop cancels it
+    owner: Option<SceneId>,
+}
+
+/// `Events` is a convenience resource to help communicate between systems/resources/layers through events.
+///
+/// Events are published either into named topics, serialized to json, or into typed channels (see [`Events::channel`])
+/// storing the values themselves, which is the way to go for in-process events.
 #[derive(Default)]
 pub struct Events {
     topics: HashMap<String, Topic>,
-    subscribers: HashMap<SubscriberId, (String, PollConfiguration, Cursor)>,
+    /// Typed channels, by type of event
+    channels: HashMap<TypeId, Box<dyn TopicStorage>>,
+    subscribers: HashMap<SubscriberId, (TopicKey, PollConfiguration, Cursor)>,
+    next_subscriber_id: SubscriberId,
+    /// Scenes that created the subscriptions, and whose stop deletes them
+    subscription_owners: HashMap<SubscriberId, SceneId>,
+    scheduled: HashMap<ScheduledEventId, ScheduledEvent>,
+    next_scheduled_id: ScheduledEventId,
+    /// Scene owning the subscriptions and scheduled events created now
+    pub(crate) scene_owner: Option<SceneId>,
 }
 
 impl Events {
@@ -70,60 +111,182 @@ impl Events {
         }
     }
 
+    /// Publish `event` into the topic `topic_name` once `delay` has elapsed on the engine clock.
+    /// The returned id can be used to cancel the publication, see [`Events::cancel`].
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use scion::core::world::GameData;
+    ///
+    /// fn drop_bomb(data: &mut GameData) {
+    ///     let _explosion = data.events().publish_after("explosions", (4, 2), Duration::from_secs(2));
+    /// }
+    /// ```
+    pub fn publish_after<T>(&mut self, topic_name: &str, event: T, delay: Duration) -> Result<ScheduledEventId, EventError>
+    where
+        T: ser::Serialize,
+    {
+        let publisher = self.topic_publisher(topic_name, event)?;
+        Ok(self.schedule(publisher, delay, None))
+    }
+
+    /// Publish `event` into the topic `topic_name` each time `period` elapses on the engine clock, until it is cancelled
+    pub fn publish_every<T>(&mut self, topic_name: &str, event: T, period: Duration) -> Result<ScheduledEventId, EventError>
+    where
+        T: ser::Serialize,
+    {
+        assert!(!period.is_zero(), "The period of a repeated event must be greater than zero");
+        let publisher = self.topic_publisher(topic_name, event)?;
+        Ok(self.schedule(publisher, period, Some(period)))
+    }
+
+    /// Cancels the scheduled publication `scheduled_id`
+    pub fn cancel(&mut self, scheduled_id: &ScheduledEventId) -> Result<(), EventError> {
+        self.scheduled.remove(scheduled_id).map(|_| ()).ok_or(EventError::ScheduledEventIdDoesNotExist)
+    }
+
     /// Creates a subscription to the topic `topic_name` using `poll_configuration`
     pub fn subscribe(
         &mut self,
         topic_name: &str,
         poll_configuration: PollConfiguration,
     ) -> Result<SubscriberId, EventError> {
-        if !self.topics.contains_key(topic_name) {
-            Err(EventError::TopicDoesNotExist)
-        } else {
-            let next_id = self.subscribers.keys().max().map_or(0, |r| r + 1);
-            self.subscribers.insert(
-                next_id,
-                (
-                    topic_name.to_string(),
-                    poll_configuration,
-                    self.topics
-                        .get(topic_name)
-                        .expect("A topic is missing, but is identified as existing")
-                        .messages
-                        .len(),
-                ),
-            );
-            Ok(next_id)
+        let cursor = self.topics.get(topic_name).ok_or(EventError::TopicDoesNotExist)?.messages.len();
+        Ok(self.add_subscriber(TopicKey::Named(topic_name.to_string()), poll_configuration, cursor))
+    }
+
+    /// Creates the typed channel of the events of type `T` using `topic_configuration`.
+    /// Channels are otherwise created with the default configuration the first time they are used.
+    pub fn create_channel<T: Clone + Send + Sync + 'static>(
+        &mut self,
+        topic_configuration: TopicConfiguration,
+    ) -> Result<(), EventError> {
+        if self.channels.contains_key(&TypeId::of::<T>()) {
+            return Err(EventError::TopicAlreadyExist);
+        }
+        self.channels.insert(TypeId::of::<T>(), Box::new(Topic::<T>::new(type_name::<T>().to_string(), topic_configuration)));
+        Ok(())
+    }
+
+    /// Returns the typed channel of the events of type `T`. Events published into a channel are stored as they are,
+    /// without serialization, and each subscriber polls a clone of them.
+    /// ```
+    /// use scion::core::resources::events::{Events, PollConfiguration};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Damage(u32);
+    ///
+    /// let mut events = Events::default();
+    /// let subscriber = events.channel::<Damage>().subscribe(PollConfiguration::default());
+    /// events.channel::<Damage>().publish(Damage(3));
+    /// assert_eq!(Some(Damage(3)), events.channel::<Damage>().poll(&subscriber).unwrap().pop_front());
+    /// assert!(events.channel::<u32>().poll(&subscriber).is_err());
+    /// ```
+    pub fn channel<T: Clone + Send + Sync + 'static>(&mut self) -> Channel<'_, T> {
+        if !self.channels.contains_key(&TypeId::of::<T>()) {
+            let _r = self.create_channel::<T>(TopicConfiguration::default());
         }
+        Channel { events: self, _event: PhantomData }
     }
 
-    /// Retrieves a list of events using `subscriber_id` subscription to a topic
+    /// Deletes the subscription `subscriber_id`
+    pub fn unsubscribe(&mut self, subscriber_id: &SubscriberId) -> Result<(), EventError> {
+        self.subscription_owners.remove(subscriber_id);
+        self.subscribers.remove(subscriber_id).map(|_| ()).ok_or(EventError::SubscriberIdDoesNotExist)
+    }
+
+    /// Keeps the subscription `subscriber_id` when the scene that created it stops
+    pub fn set_persistent(&mut self, subscriber_id: &SubscriberId) -> Result<(), EventError> {
+        if !self.subscribers.contains_key(subscriber_id) {
+            return Err(EventError::SubscriberIdDoesNotExist);
+        }
+        self.subscription_owners.remove(subscriber_id);
+        Ok(())
+    }
+
+    /// Deletes the subscriptions created by `scene`, unless they were made persistent, and cancels its scheduled events
+    pub(crate) fn unsubscribe_scene(&mut self, scene: SceneId) {
+        self.scheduled.retain(|_, scheduled| scheduled.owner != Some(scene));
+        let owned: Vec<SubscriberId> =
+            self.subscription_owners.iter().filter(|(_, owner)| **owner == scene).map(|(id, _)| *id).collect();
+        owned.iter().for_each(|id| {
+            let _r = self.unsubscribe(id);
+        });
+    }
+
+    /// Retrieves a list of events using `subscriber_id` subscription to a topic.
+    /// When the next event can't be deserialized into `T`, the events before it are returned, then the next poll
+    /// returns a [`EventError::TypeMismatch`] and skips it.
     pub fn poll<T>(&mut self, subscriber_id: &SubscriberId) -> Result<VecDeque<T>, EventError>
     where
         T: DeserializeOwned,
     {
-        if self.subscribers.contains_key(subscriber_id) {
-            let (topic_name, poll_configuration, cursor) = self
-                .subscribers
-                .get_mut(subscriber_id)
-                .expect("A topic is missing, but is identified as existing");
-            let topic = self
-                .topics
-                .get(topic_name)
-                .expect("A subscriber Id has been linked to a non existing topic");
-
-            let slice_start = *cursor;
-            let slice_end = if topic.messages.len() - *cursor < poll_configuration.max_messages {
-                topic.messages.len()
-            } else {
-                *cursor + poll_configuration.max_messages
-            };
-            let target_slice = &topic.messages[slice_start..slice_end];
-            let polled: VecDeque<T> =
-                target_slice.iter().filter_map(|message| from_str(message).ok()).collect();
-            *cursor += polled.len();
-            return Ok(polled);
+        let (topic_key, poll_configuration, cursor) =
+            self.subscribers.get_mut(subscriber_id).ok_or(EventError::SubscriberIdDoesNotExist)?;
+        let TopicKey::Named(topic_name) = topic_key else {
+            return Err(EventError::TypeMismatch(format!(
+                "Subscription {} is a subscription to a typed channel, use `Events::channel` to poll it",
+                subscriber_id
+            )));
+        };
+        let topic = self
+            .topics
+            .get(topic_name)
+            .expect("A subscriber Id has been linked to a non existing topic");
+
+        let slice_end = topic.messages.len().min(*cursor + poll_configuration.max_messages);
+        let mut polled = VecDeque::new();
+        for message in topic.messages[*cursor..slice_end].iter() {
+            match from_str(message) {
+                Ok(event) => polled.push_back(event),
+                Err(e) if polled.is_empty() => {
+                    *cursor += 1;
+                    return Err(EventError::TypeMismatch(format!(
+                        "Event '{}' of topic '{}' is not a {}: {}",
+                        message,
+                        topic_name,
+                        type_name::<T>(),
+                        e
+                    )));
+                }
+                Err(_) => break,
+            }
+        }
+        *cursor += polled.len();
+        Ok(polled)
+    }
+
+    /// Moves the cursor of `subscriber_id` to the end of its topic, without reading the events.
+    /// Returns the number of events that were skipped.
+    pub fn mark_read(&mut self, subscriber_id: &SubscriberId) -> Result<usize, EventError> {
+        let (topic_key, _, cursor) =
+            self.subscribers.get_mut(subscriber_id).ok_or(EventError::SubscriberIdDoesNotExist)?;
+        let length = topic_length(&self.topics, &self.channels, topic_key);
+        let unread = length - *cursor;
+        *cursor = length;
+        Ok(unread)
+    }
+
+    /// Advances the scheduled events by `delta_duration`, publishing the ones that are due
+    pub(crate) fn add_delta_duration(&mut self, delta_duration: Duration) {
+        if delta_duration.is_zero() || self.scheduled.is_empty() {
+            return;
         }
-        Err(EventError::SubscriberIdDoesNotExist)
+        let mut scheduled: Vec<(ScheduledEventId, ScheduledEvent)> = self.scheduled.drain().collect();
+        scheduled.sort_by_key(|(id, _)| *id);
+        scheduled.retain_mut(|(_, event)| {
+            let mut elapsed = delta_duration;
+            while event.remaining <= elapsed {
+                elapsed -= event.remaining;
+                (event.publisher)(self);
+                match event.period {
+                    Some(period) => event.remaining = period,
+                    None => return false,
+                }
+            }
+            event.remaining -= elapsed;
+            true
+        });
+        self.scheduled.extend(scheduled);
     }
 
     pub(crate) fn cleanup(&mut self) {
@@ -131,10 +294,60 @@ impl Events {
         self.cleanup_topics_outdated()
     }
 
+    fn add_subscriber(&mut self, topic_key: TopicKey, poll_configuration: PollConfiguration, cursor: Cursor) -> SubscriberId {
+        let next_id = self.next_subscriber_id;
+        self.next_subscriber_id += 1;
+        if let Some(owner) = self.scene_owner {
+            self.subscription_owners.insert(next_id, owner);
+        }
+        self.subscribers.insert(next_id, (topic_key, poll_configuration, cursor));
+        next_id
+    }
+
+    fn topic_publisher<T: ser::Serialize>(
+        &self,
+        topic_name: &str,
+        event: T,
+    ) -> Result<Publisher, EventError> {
+        if !self.topics.contains_key(topic_name) {
+            return Err(EventError::TopicDoesNotExist);
+        }
+        let topic_name = topic_name.to_string();
+        let message = to_string(&event).unwrap();
+        Ok(Box::new(move |events: &mut Events| {
+            if let Some(topic) = events.topics.get_mut(&topic_name) {
+                topic.publish(message.clone());
+            }
+        }))
+    }
+
+    fn schedule(
+        &mut self,
+        publisher: Publisher,
+        delay: Duration,
+        period: Option<Duration>,
+    ) -> ScheduledEventId {
+        let next_id = self.next_scheduled_id;
+        self.next_scheduled_id += 1;
+        self.scheduled.insert(next_id, ScheduledEvent { publisher, remaining: delay, period, owner: self.scene_owner });
+        next_id
+    }
+
+    fn topic_storage_mut(&mut self, topic_key: &TopicKey) -> &mut dyn TopicStorage {
+        match topic_key {
+            TopicKey::Named(name) => self.topics.get_mut(name).expect("A subscriber is referencing a non existing topic"),
+            TopicKey::Channel(type_id) => self
+                .channels
+                .get_mut(type_id)
+                .expect("A subscriber is referencing a non existing channel")
+                .as_mut(),
+        }
+    }
+
     fn cleanup_topics_outdated(&mut self) {
         let mut min_cursor_for_topics = HashMap::new();
         self.subscribers.values_mut().for_each(|(topic, _, cursor)| {
-            let current = min_cursor_for_topics.entry(topic.to_string()).or_insert(*cursor);
+            let current = min_cursor_for_topics.entry(topic.clone()).or_insert(*cursor);
             if current > cursor {
                 *current = *cursor;
             }
@@ -145,17 +358,17 @@ impl Events {
                 .values_mut()
                 .filter(|(t, _, _)| t == topic)
                 .for_each(|(_, _, cursor)| *cursor -= *min_cursor);
-            self.topics
-                .get_mut(topic)
-                .expect("A subscriber is referencing a non existing topic")
-                .cleanup_outdated(*min_cursor);
+            self.topic_storage_mut(topic).cleanup_outdated(*min_cursor);
         })
     }
 
     fn cleanup_topics_overflow(&mut self) {
         let mut overflow_counts = HashMap::new();
         self.topics.iter_mut().for_each(|(name, topic)| {
-            overflow_counts.insert(name.clone(), topic.cleanup_overflow());
+            overflow_counts.insert(TopicKey::Named(name.clone()), topic.cleanup_overflow());
+        });
+        self.channels.iter_mut().for_each(|(type_id, channel)| {
+            overflow_counts.insert(TopicKey::Channel(*type_id), channel.cleanup_overflow());
         });
 
         self.subscribers.iter_mut().for_each(|(_id, subscription)| {
@@ -171,9 +384,87 @@ impl Events {
     }
 }
 
+/// Handle to the typed channel of the events of type `T`, returned by [`Events::channel`]
+pub struct Channel<'a, T> {
+    events: &'a mut Events,
+    _event: PhantomData<T>,
+}
+
+impl<'a, T: Clone + Send + Sync + 'static> Channel<'a, T> {
+    /// Publish `event` into the channel
+    pub fn publish(&mut self, event: T) {
+        self.topic_mut().publish(event);
+    }
+
+    /// Publish `event` into the channel once `delay` has elapsed on the engine clock
+    pub fn publish_after(&mut self, event: T, delay: Duration) -> ScheduledEventId {
+        self.events.schedule(channel_publisher(event), delay, None)
+    }
+
+    /// Publish `event` into the channel each time `period` elapses on the engine clock, until it is cancelled
+    pub fn publish_every(&mut self, event: T, period: Duration) -> ScheduledEventId {
+        assert!(!period.is_zero(), "The period of a repeated event must be greater than zero");
+        self.events.schedule(channel_publisher(event), period, Some(period))
+    }
+
+    /// Creates a subscription to the channel using `poll_configuration`
+    pub fn subscribe(&mut self, poll_configuration: PollConfiguration) -> SubscriberId {
+        let cursor = self.topic_mut().messages.len();
+        self.events.add_subscriber(TopicKey::Channel(TypeId::of::<T>()), poll_configuration, cursor)
+    }
+
+    /// Retrieves the events published since the last poll of `subscriber_id`, which must be a subscription to this channel
+    pub fn poll(&mut self, subscriber_id: &SubscriberId) -> Result<VecDeque<T>, EventError> {
+        let events = &mut *self.events;
+        let (topic_key, poll_configuration, cursor) =
+            events.subscribers.get_mut(subscriber_id).ok_or(EventError::SubscriberIdDoesNotExist)?;
+        if *topic_key != TopicKey::Channel(TypeId::of::<T>()) {
+            return Err(EventError::TypeMismatch(format!(
+                "Subscription {} is not a subscription to the channel of {}",
+                subscriber_id,
+                type_name::<T>()
+            )));
+        }
+        let topic = channel_topic::<T>(&events.channels);
+        let slice_end = topic.messages.len().min(*cursor + poll_configuration.max_messages);
+        let polled: VecDeque<T> = topic.messages[*cursor..slice_end].iter().cloned().collect();
+        *cursor = slice_end;
+        Ok(polled)
+    }
+
+    fn topic_mut(&mut self) -> &mut Topic<T> {
+        self.events
+            .channels
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|channel| channel.as_any_mut().downcast_mut::<Topic<T>>())
+            .expect("A channel is missing, but is identified as existing")
+    }
+}
+
+fn channel_publisher<T: Clone + Send + Sync + 'static>(event: T) -> Publisher {
+    Box::new(move |events: &mut Events| events.channel::<T>().publish(event.clone()))
+}
+
+fn channel_topic<T: 'static>(channels: &HashMap<TypeId, Box<dyn TopicStorage>>) -> &Topic<T> {
+    channels
+        .get(&TypeId::of::<T>())
+        .and_then(|channel| channel.as_any().downcast_ref::<Topic<T>>())
+        .expect("A subscriber is referencing a non existing channel")
+}
+
+fn topic_length(topics: &HashMap<String, Topic>, channels: &HashMap<TypeId, Box<dyn TopicStorage>>, topic_key: &TopicKey) -> usize {
+    match topic_key {
+        TopicKey::Named(name) => topics.get(name).expect("A subscriber Id has been linked to a non existing topic").messages.len(),
+        TopicKey::Channel(type_id) => channels.get(type_id).expect("A subscriber is referencing a non existing channel").len(),
+    }
+}
+
 #[cfg(test)]
 mod event_tests {
-    use crate::core::resources::events::{Events, PollConfiguration, TopicConfiguration};
+    use std::any::TypeId;
+    use std::time::Duration;
+
+    use crate::core::resources::events::{EventError, Events, PollConfiguration, TopicConfiguration};
 
     #[test]
     fn create_topic_test() {
@@ -261,4 +552,71 @@ mod event_tests {
         event.cleanup();
         assert_eq!(2, event.topics.get("test_topic").unwrap().messages.len());
     }
+
+    #[test]
+    fn channel_test() {
+        let mut event = Events::default();
+        let _r = event.create_channel::<String>(TopicConfiguration { limit: 2 });
+        assert!(event.create_channel::<String>(TopicConfiguration::default()).is_err());
+        let subscriber_id = event.channel::<String>().subscribe(PollConfiguration { max_messages: 2 });
+        event.channel::<String>().publish("first".to_string());
+        event.channel::<String>().publish("second".to_string());
+        event.channel::<String>().publish("third".to_string());
+
+        let poll_result = event.channel::<String>().poll(&subscriber_id).unwrap();
+        assert_eq!(vec!["first", "second"], poll_result.iter().collect::<Vec<_>>());
+        event.cleanup();
+        assert_eq!(1, event.channels.get(&TypeId::of::<String>()).unwrap().len());
+        let poll_result = event.channel::<String>().poll(&subscriber_id).unwrap();
+        assert_eq!(vec!["third"], poll_result.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn type_mismatch_test() {
+        let mut event = Events::default();
+        let _r = event.create_topic("test_topic", TopicConfiguration::default());
+        let subscriber_id = event.subscribe("test_topic", PollConfiguration::default()).unwrap();
+        let channel_subscriber_id = event.channel::<u32>().subscribe(PollConfiguration::default());
+
+        let _r = event.publish("test_topic", 4);
+        let _r = event.publish("test_topic", "not a number");
+        let _r = event.publish("test_topic", 12);
+        assert_eq!(vec![4], event.poll::<usize>(&subscriber_id).unwrap().into_iter().collect::<Vec<_>>());
+        assert!(matches!(event.poll::<usize>(&subscriber_id), Err(EventError::TypeMismatch(_))));
+        assert_eq!(vec![12], event.poll::<usize>(&subscriber_id).unwrap().into_iter().collect::<Vec<_>>());
+
+        event.channel::<u32>().publish(3);
+        assert!(matches!(event.poll::<u32>(&channel_subscriber_id), Err(EventError::TypeMismatch(_))));
+        assert!(matches!(event.channel::<u64>().poll(&channel_subscriber_id), Err(EventError::TypeMismatch(_))));
+        assert!(matches!(event.channel::<u32>().poll(&subscriber_id), Err(EventError::TypeMismatch(_))));
+        assert_eq!(Some(3), event.channel::<u32>().poll(&channel_subscriber_id).unwrap().pop_front());
+    }
+
+    #[test]
+    fn scheduled_events_test() {
+        let mut event = Events::default();
+        let _r = event.create_topic("test_topic", TopicConfiguration::default());
+        let subscriber_id = event.subscribe("test_topic", PollConfiguration::default()).unwrap();
+        let channel_subscriber_id = event.channel::<u32>().subscribe(PollConfiguration::default());
+        assert!(event.publish_after("unknown", 1, Duration::from_secs(1)).is_err());
+
+        let _r = event.publish_after("test_topic", 1, Duration::from_millis(1500));
+        let repeated = event.publish_every("test_topic", 2, Duration::from_secs(1)).unwrap();
+        let cancelled = event.channel::<u32>().publish_after(3, Duration::from_secs(1));
+        let _r = event.channel::<u32>().publish_every(4, Duration::from_millis(500));
+
+        event.add_delta_duration(Duration::ZERO);
+        event.add_delta_duration(Duration::from_millis(700));
+        assert_eq!(vec![4], event.channel::<u32>().poll(&channel_subscriber_id).unwrap().into_iter().collect::<Vec<_>>());
+        assert!(event.cancel(&cancelled).is_ok());
+        assert!(event.cancel(&cancelled).is_err());
+
+        event.add_delta_duration(Duration::from_millis(2400));
+        assert_eq!(vec![1, 2, 2, 2], event.poll::<u32>(&subscriber_id).unwrap().into_iter().collect::<Vec<_>>());
+        assert_eq!(vec![4; 5], event.channel::<u32>().poll(&channel_subscriber_id).unwrap().into_iter().collect::<Vec<_>>());
+
+        let _r = event.cancel(&repeated);
+        event.add_delta_duration(Duration::from_secs(1));
+        assert!(event.poll::<u32>(&subscriber_id).unwrap().is_empty());
+    }
 }
@@ -1,10 +1,13 @@
-pub(crate) struct Topic {
+use std::any::Any;
+
+/// Messages of a topic, published in order
+pub(crate) struct Topic<M = String> {
     _name: String,
     configuration: TopicConfiguration,
-    pub(crate) messages: Vec<String>,
+    pub(crate) messages: Vec<M>,
 }
 
-impl Topic {
+impl<M> Topic<M> {
     pub(crate) fn new(name: String, configuration: TopicConfiguration) -> Self {
         Self {
             _name: name.to_string(),
@@ -13,16 +16,14 @@ impl Topic {
         }
     }
 
-    pub(crate) fn publish(&mut self, message: String) {
+    pub(crate) fn publish(&mut self, message: M) {
         self.messages.push(message);
     }
 
     pub(crate) fn cleanup_overflow(&mut self) -> usize {
         if self.messages.len() > self.configuration.limit {
             let overflow = self.messages.len() - self.configuration.limit;
-            self.messages = self.messages
-                [self.messages.len() - self.configuration.limit..self.messages.len()]
-                .to_vec();
+            self.messages.drain(0..overflow);
             overflow
         } else {
             0
@@ -31,11 +32,42 @@ impl Topic {
 
     pub(crate) fn cleanup_outdated(&mut self, min_index: usize) {
         if min_index > 0 {
-            self.messages = self.messages[min_index..self.messages.len()].to_vec();
+            self.messages.drain(0..min_index);
         }
     }
 }
 
+/// Type erased topic, used to store the typed channels of every event type together
+pub(crate) trait TopicStorage: Send + Sync {
+    fn len(&self) -> usize;
+    fn cleanup_overflow(&mut self) -> usize;
+    fn cleanup_outdated(&mut self, min_index: usize);
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<M: Send + Sync + 'static> TopicStorage for Topic<M> {
+    fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    fn cleanup_overflow(&mut self) -> usize {
+        Topic::cleanup_overflow(self)
+    }
+
+    fn cleanup_outdated(&mut self, min_index: usize) {
+        Topic::cleanup_outdated(self, min_index)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
 #[derive(Clone)]
 pub struct TopicConfiguration {
     pub limit: usize,
@@ -53,7 +85,7 @@ mod topic_tests {
 
     #[test]
     fn topic_cleanup_overflow_test() {
-        let mut topic = Topic::new("test".to_string(), TopicConfiguration { limit: 3 });
+        let mut topic: Topic = Topic::new("test".to_string(), TopicConfiguration { limit: 3 });
         for _i in 0..4 {
             topic.publish("1".to_string());
         }
@@ -70,7 +102,7 @@ mod topic_tests {
 
     #[test]
     fn topic_cleanup_outdated_test() {
-        let mut topic = Topic::new("test".to_string(), TopicConfiguration { limit: 3 });
+        let mut topic: Topic = Topic::new("test".to_string(), TopicConfiguration { limit: 3 });
         for _i in 0..3 {
             topic.publish("1".to_string());
         }
@@ -56,6 +56,8 @@ impl TrueTypeData {
 #[derive(Default)]
 pub(crate) struct FontAtlas {
     atlas: HashMap<String, TrueTypeData>,
+    /// Content of the font files preloaded by the scenes, by path
+    font_files: HashMap<String, Vec<u8>>,
 }
 
 impl FontAtlas {
@@ -74,6 +76,14 @@ impl FontAtlas {
         None
     }
 
+    pub(crate) fn add_font_file(&mut self, path: String, font_file: Vec<u8>) {
+        self.font_files.insert(path, font_file);
+    }
+
+    pub(crate) fn get_font_file(&self, path: &str) -> Option<&Vec<u8>> {
+        self.font_files.get(path)
+    }
+
     pub fn add_texture(&mut self, font: String, font_size: usize, font_color: &Color, data: TrueTypeData) {
         let key = format!("{:?}_{:?}_{:?}", font, font_size, font_color.to_string());
         self.atlas.insert(key, data);
@@ -83,63 +93,66 @@ impl FontAtlas {
 pub(crate) fn generate_bitmap(font: crate::graphics::components::ui::font::Font, font_size: usize, font_color: &Color) -> Result<TrueTypeData, ScionError> {
     if let crate::graphics::components::ui::font::Font::TrueType { font_path } = font {
         return match read_file(Path::new(&font_path)) {
-            Ok(res) => {
-                let font = FontVec::try_from_vec(res);
-                if let Ok(font_vec) = font {
-                    let mut glyphs = Vec::<Glyph>::new();
-                    let scale = PxScale::from(font_size as f32);
-                    let scaled_font = font_vec.as_scaled(scale);
-                    layout_paragraph(scaled_font, point(20.0, 20.0), 9999.0, TEXT, &mut glyphs);
-                    let glyphs_height = scaled_font.height().ceil() as u32;
-                    let glyphs_width = {
-                        let min_x = glyphs.first().unwrap().position.x;
-                        let last_glyph = glyphs.last().unwrap();
-                        let max_x = last_glyph.position.x + scaled_font.h_advance(last_glyph.id);
-                        (max_x - min_x).ceil() as u32
-                    };
-
-                    let mut character_positions = HashMap::<char, CharacterPosition>::new();
-                    let mut min_y = 99999.;
-                    let mut image = DynamicImage::new_rgba8(glyphs_width + 40, glyphs_height + 40).to_rgba8();
-                    for (pos, glyph) in glyphs.drain(0..glyphs.len()).enumerate() {
-                        if let Some(outlined) = scaled_font.outline_glyph(glyph) {
-                            let bounds = outlined.px_bounds();
-                            outlined.draw(|x, y, v| {
-                                let px = image.get_pixel_mut(x + bounds.min.x as u32, y + bounds.min.y as u32);
-                                *px = Rgba([
-                                    font_color.red(),
-                                    font_color.green(),
-                                    font_color.blue(),
-                                    px.0[3].saturating_add((v * 255.0) as u8),
-                                ]);
-                            });
-                            if min_y > bounds.min.y {
-                                min_y = bounds.min.y;
-                            }
-                            let char_pos = CharacterPosition::new(bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y);
-                            character_positions.insert(TEXT.to_string().chars().nth(pos).unwrap(), char_pos);
-                        }
-                    }
-                    return Ok(TrueTypeData {
-                        texture: Some(Texture {
-                            bytes: image.to_vec(),
-                            width: glyphs_width + 40,
-                            height: glyphs_height + 40,
-                        }),
-                        width: glyphs_width + 40,
-                        height: glyphs_height + 40,
-                        min_y,
-                        character_positions,
-                    });
-                }
-                Err(ScionError::new("Impossible to read font"))
-            }
+            Ok(res) => generate_bitmap_from_bytes(res, font_size, font_color),
             Err(_) => Err(ScionError::new("Impossible to find font file"))
         };
     }
     Err(ScionError::new("Wrong type sent to bitmap generation"))
 }
 
+/// Generates the bitmap of a true type font from the content of its file
+pub(crate) fn generate_bitmap_from_bytes(font_file: Vec<u8>, font_size: usize, font_color: &Color) -> Result<TrueTypeData, ScionError> {
+    let font = FontVec::try_from_vec(font_file);
+    if let Ok(font_vec) = font {
+        let mut glyphs = Vec::<Glyph>::new();
+        let scale = PxScale::from(font_size as f32);
+        let scaled_font = font_vec.as_scaled(scale);
+        layout_paragraph(scaled_font, point(20.0, 20.0), 9999.0, TEXT, &mut glyphs);
+        let glyphs_height = scaled_font.height().ceil() as u32;
+        let glyphs_width = {
+            let min_x = glyphs.first().unwrap().position.x;
+            let last_glyph = glyphs.last().unwrap();
+            let max_x = last_glyph.position.x + scaled_font.h_advance(last_glyph.id);
+            (max_x - min_x).ceil() as u32
+        };
+
+        let mut character_positions = HashMap::<char, CharacterPosition>::new();
+        let mut min_y = 99999.;
+        let mut image = DynamicImage::new_rgba8(glyphs_width + 40, glyphs_height + 40).to_rgba8();
+        for (pos, glyph) in glyphs.drain(0..glyphs.len()).enumerate() {
+            if let Some(outlined) = scaled_font.outline_glyph(glyph) {
+                let bounds = outlined.px_bounds();
+                outlined.draw(|x, y, v| {
+                    let px = image.get_pixel_mut(x + bounds.min.x as u32, y + bounds.min.y as u32);
+                    *px = Rgba([
+                        font_color.red(),
+                        font_color.green(),
+                        font_color.blue(),
+                        px.0[3].saturating_add((v * 255.0) as u8),
+                    ]);
+                });
+                if min_y > bounds.min.y {
+                    min_y = bounds.min.y;
+                }
+                let char_pos = CharacterPosition::new(bounds.min.x, bounds.min.y, bounds.max.x, bounds.max.y);
+                character_positions.insert(TEXT.to_string().chars().nth(pos).unwrap(), char_pos);
+            }
+        }
+        return Ok(TrueTypeData {
+            texture: Some(Texture {
+                bytes: image.to_vec(),
+                width: glyphs_width + 40,
+                height: glyphs_height + 40,
+            }),
+            width: glyphs_width + 40,
+            height: glyphs_height + 40,
+            min_y,
+            character_positions,
+        });
+    }
+    Err(ScionError::new("Impossible to read font"))
+}
+
 /// Simple paragraph layout for glyphs into `target`.
 ///
 /// This is for testing and examples.
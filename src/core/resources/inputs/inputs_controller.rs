@@ -1,10 +1,14 @@
 //! Everything that is relatives to the core.resources.inputs.
 
+use std::time::Duration;
+
 use crate::core::resources::inputs::{
     keyboard::Keyboard,
     mouse::{Mouse, MouseEvent},
+    recording::{InputRecorder, InputRecording, RecordedInput},
     types::{Input, InputState, KeyboardEvent, KeyCode, Shortcut},
 };
+use crate::core::resources::random::Random;
 
 /// A resource updated by `Scion` to keep track of the core.resources.inputs
 /// Can be used in any system.
@@ -12,6 +16,7 @@ use crate::core::resources::inputs::{
 pub struct InputsController {
     mouse: Mouse,
     keyboard: Keyboard,
+    recorder: InputRecorder,
 }
 
 impl InputsController {
@@ -126,6 +131,70 @@ impl InputsController {
             })
     }
 
+    /// Starts recording every input fed into the controller, with the number of the frame it is fed in, from the next frame.
+    /// The seed of the `Random` resource is stored in the recording, and its streams are restarted, to reproduce the session.
+    /// ```no_run
+    /// use scion::core::world::GameData;
+    ///
+    /// fn record_session(data: &mut GameData) {
+    ///     data.inputs().start_recording();
+    /// }
+    /// ```
+    pub fn start_recording(&mut self) {
+        self.recorder.start_recording();
+    }
+
+    /// Stops the recording and returns it, if a recording was started
+    pub fn stop_recording(&mut self) -> Option<InputRecording> {
+        self.recorder.stop_recording()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_recording()
+    }
+
+    /// Replays `recording`, injecting its inputs frame by frame, starting with the next frame.
+    /// Inputs from the window are ignored until the replay is over or stopped. The `Random` resource is reseeded
+    /// with the seed of the recording to draw the same random numbers.
+    /// ```no_run
+    /// use std::path::Path;
+    /// use scion::core::resources::inputs::recording::InputRecording;
+    /// use scion::core::world::GameData;
+    ///
+    /// fn replay_bug_report(data: &mut GameData) {
+    ///     let recording = InputRecording::load(Path::new("bug_report.json")).unwrap();
+    ///     data.inputs().replay(recording);
+    /// }
+    /// ```
+    pub fn replay(&mut self, recording: InputRecording) {
+        self.recorder.replay(recording);
+    }
+
+    pub fn stop_replay(&mut self) {
+        self.recorder.stop_replay();
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.recorder.is_replaying()
+    }
+
+    /// Called before advancing the time of a new frame, to synchronize the seed of `random` with a new recording or replay
+    pub(crate) fn sync_random(&mut self, random: &mut Random) {
+        self.recorder.sync_random(random);
+    }
+
+    /// Called before advancing the time of a new frame, to record its measured duration or to get the replayed one
+    pub(crate) fn frame_duration(&mut self, measured: Duration) -> Duration {
+        self.recorder.frame_duration(measured)
+    }
+
+    /// Called at the beginning of each frame, to record the inputs of the previous frame or to inject the replayed ones
+    pub(crate) fn start_frame(&mut self) {
+        for input in self.recorder.next_frame() {
+            self.apply(input);
+        }
+    }
+
     fn all_events_for_state(&self, input_state: InputState) -> Vec<Input> {
         let mut inputs = self.keyboard.all_keys_at_state(input_state);
         let mut mouse_inputs = self.mouse.all_click_at_state(input_state);
@@ -150,14 +219,31 @@ impl InputsController {
     }
 
     pub(crate) fn set_mouse_position(&mut self, x: f64, y: f64) {
-        self.mouse.set_position(x, y);
+        self.feed(RecordedInput::MousePosition(x, y));
     }
 
     pub(crate) fn add_click_event(&mut self, event: MouseEvent) {
-        self.mouse.add_click_event(event);
+        self.feed(RecordedInput::Mouse(event));
     }
 
     pub(crate) fn add_keyboard_event(&mut self, event: KeyboardEvent) {
-        self.keyboard.add_keyboard_event(event);
+        self.feed(RecordedInput::Keyboard(event));
+    }
+
+    /// Applies an input coming from the window, unless inputs are being replayed
+    fn feed(&mut self, input: RecordedInput) {
+        if self.recorder.is_replaying() {
+            return;
+        }
+        self.recorder.record(input.clone());
+        self.apply(input);
+    }
+
+    fn apply(&mut self, input: RecordedInput) {
+        match input {
+            RecordedInput::Keyboard(event) => self.keyboard.add_keyboard_event(event),
+            RecordedInput::Mouse(event) => self.mouse.add_click_event(event),
+            RecordedInput::MousePosition(x, y) => self.mouse.set_position(x, y),
+        }
     }
 }
@@ -1,4 +1,5 @@
 pub mod inputs_controller;
 pub mod keyboard;
 pub mod mouse;
+pub mod recording;
 pub mod types;
@@ -0,0 +1,282 @@
+//! Recording of the inputs fed into the `InputsController`, to replay a session frame by frame.
+
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use log::info;
+use serde::{Deserialize, Serialize};
+
+use crate::core::resources::inputs::{mouse::MouseEvent, types::KeyboardEvent};
+use crate::core::resources::random::Random;
+
+/// `RecordingError` represents the errors returned when writing or reading an input recording file
+#[derive(Debug)]
+pub enum RecordingError {
+    /// The file can't be read or written. Contains the io error message
+    IoError(String),
+    /// The file isn't a valid recording. Contains the serialization error message
+    SerializationError(String),
+}
+
+/// An input event fed into the `InputsController`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum RecordedInput {
+    Keyboard(KeyboardEvent),
+    Mouse(MouseEvent),
+    MousePosition(f64, f64),
+}
+
+/// Inputs fed during a frame
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RecordedFrame {
+    /// Number of the frame, counted from the start of the recording
+    frame: u64,
+    inputs: Vec<RecordedInput>,
+}
+
+/// `InputRecording` holds every input fed into the `InputsController` during a session, with the number of the frame
+/// they were fed in, the real duration of each frame, and the seed of the random numbers used by the game during this
+/// session. The replayed frames last the recorded durations, so the timers, animations, scheduled events and
+/// fixed updates run the same.
+/// See [`crate::core::resources::inputs::inputs_controller::InputsController::start_recording`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InputRecording {
+    seed: u64,
+    /// Number of frames recorded
+    frames: u64,
+    /// Real duration of each recorded frame
+    #[serde(default)]
+    durations: Vec<Duration>,
+    inputs: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    fn new(seed: u64) -> Self {
+        Self { seed, frames: 0, durations: Vec::new(), inputs: Vec::new() }
+    }
+
+    /// Seed of the random numbers used during the recorded session
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Number of frames recorded
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// Writes the recording into the json file `path`
+    pub fn save(&self, path: &Path) -> Result<(), RecordingError> {
+        let content = serde_json::to_vec(self).map_err(|e| RecordingError::SerializationError(e.to_string()))?;
+        fs::write(path, content).map_err(|e| RecordingError::IoError(e.to_string()))
+    }
+
+    /// Reads the recording written into the json file `path`
+    pub fn load(path: &Path) -> Result<InputRecording, RecordingError> {
+        let content = fs::read(path).map_err(|e| RecordingError::IoError(e.to_string()))?;
+        serde_json::from_slice(&content).map_err(|e| RecordingError::SerializationError(e.to_string()))
+    }
+}
+
+enum RecorderState {
+    Idle,
+    Recording { recording: InputRecording, pending: Vec<RecordedInput> },
+    Replaying { recording: InputRecording, frame: u64, next: usize },
+}
+
+/// Records the inputs of the `InputsController`, or provides the recorded inputs of each frame when replaying
+pub(crate) struct InputRecorder {
+    state: RecorderState,
+    /// Whether the `Random` resource must be synchronized with the recording started since the last frame
+    seed_pending: bool,
+}
+
+impl Default for InputRecorder {
+    fn default() -> Self {
+        Self { state: RecorderState::Idle, seed_pending: false }
+    }
+}
+
+impl InputRecorder {
+    pub(crate) fn start_recording(&mut self) {
+        info!("Starting to record inputs");
+        self.state = RecorderState::Recording { recording: InputRecording::new(0), pending: Vec::new() };
+        self.seed_pending = true;
+    }
+
+    pub(crate) fn stop_recording(&mut self) -> Option<InputRecording> {
+        match std::mem::replace(&mut self.state, RecorderState::Idle) {
+            RecorderState::Recording { recording, .. } => {
+                info!("Inputs recorded during {} frames", recording.frames);
+                Some(recording)
+            }
+            state => {
+                self.state = state;
+                None
+            }
+        }
+    }
+
+    pub(crate) fn replay(&mut self, recording: InputRecording) {
+        info!("Replaying {} frames of recorded inputs", recording.frames);
+        self.state = RecorderState::Replaying { recording, frame: 0, next: 0 };
+        self.seed_pending = true;
+    }
+
+    pub(crate) fn stop_replay(&mut self) {
+        if self.is_replaying() {
+            info!("Inputs replay stopped");
+            self.state = RecorderState::Idle;
+        }
+    }
+
+    pub(crate) fn is_recording(&self) -> bool {
+        matches!(self.state, RecorderState::Recording { .. })
+    }
+
+    pub(crate) fn is_replaying(&self) -> bool {
+        matches!(self.state, RecorderState::Replaying { .. })
+    }
+
+    pub(crate) fn record(&mut self, input: RecordedInput) {
+        if let RecorderState::Recording { pending, .. } = &mut self.state {
+            pending.push(input);
+        }
+    }
+
+    /// Stores the seed of `random` in a new recording, or gives it the seed of a new replay, and restarts its
+    /// streams so that the recorded and replayed sessions draw the same numbers
+    pub(crate) fn sync_random(&mut self, random: &mut Random) {
+        if !std::mem::take(&mut self.seed_pending) {
+            return;
+        }
+        match &mut self.state {
+            RecorderState::Idle => {}
+            RecorderState::Recording { recording, .. } => {
+                recording.seed = random.seed();
+                random.reseed(recording.seed);
+            }
+            RecorderState::Replaying { recording, .. } => random.reseed(recording.seed),
+        }
+    }
+
+    /// Returns the real duration of the frame starting : the measured one, stored when recording, or the recorded
+    /// one when replaying. Called before [`InputRecorder::next_frame`]
+    pub(crate) fn frame_duration(&mut self, measured: Duration) -> Duration {
+        match &mut self.state {
+            RecorderState::Idle => measured,
+            RecorderState::Recording { recording, .. } => {
+                recording.durations.push(measured);
+                measured
+            }
+            RecorderState::Replaying { recording, frame, .. } => {
+                recording.durations.get(*frame as usize).copied().unwrap_or(measured)
+            }
+        }
+    }
+
+    /// Starts a new frame. Stores the inputs recorded since the previous frame or, when replaying,
+    /// returns the inputs recorded for the new frame.
+    pub(crate) fn next_frame(&mut self) -> Vec<RecordedInput> {
+        match &mut self.state {
+            RecorderState::Idle => Vec::new(),
+            RecorderState::Recording { recording, pending } => {
+                push_frame(recording, std::mem::take(pending));
+                recording.frames += 1;
+                Vec::new()
+            }
+            RecorderState::Replaying { recording, frame, next } => {
+                let mut inputs = Vec::new();
+                while let Some(recorded) = recording.inputs.get(*next).filter(|recorded| recorded.frame == *frame) {
+                    inputs.extend(recorded.inputs.iter().cloned());
+                    *next += 1;
+                }
+                *frame += 1;
+                if *frame >= recording.frames {
+                    info!("Inputs replay finished");
+                    self.state = RecorderState::Idle;
+                }
+                inputs
+            }
+        }
+    }
+}
+
+/// Stores the inputs fed during the frame being recorded
+fn push_frame(recording: &mut InputRecording, inputs: Vec<RecordedInput>) {
+    if !inputs.is_empty() {
+        recording.inputs.push(RecordedFrame { frame: recording.frames, inputs });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::resources::inputs::inputs_controller::InputsController;
+    use crate::core::resources::inputs::types::{Input, InputState, KeyCode, MouseButton};
+
+    use super::*;
+
+    fn press(key: KeyCode) -> KeyboardEvent {
+        KeyboardEvent { keycode: key, state: InputState::Pressed }
+    }
+
+    #[test]
+    fn record_and_replay_test() {
+        let mut inputs = InputsController::default();
+        let millis = Duration::from_millis;
+        inputs.add_keyboard_event(press(KeyCode::A));
+        assert_eq!(millis(5), inputs.frame_duration(millis(5)));
+        inputs.start_recording();
+        inputs.sync_random(&mut Random::new(42));
+        inputs.frame_duration(millis(16));
+        inputs.start_frame();
+        inputs.reset_inputs();
+        inputs.add_keyboard_event(press(KeyCode::Space));
+        inputs.set_mouse_position(10., 20.);
+        inputs.frame_duration(millis(20));
+        inputs.start_frame();
+        inputs.reset_inputs();
+        inputs.frame_duration(millis(33));
+        inputs.start_frame();
+        inputs.reset_inputs();
+        inputs.add_click_event(MouseEvent { button: MouseButton::Left, state: InputState::Pressed });
+        inputs.frame_duration(millis(16));
+        inputs.start_frame();
+        inputs.reset_inputs();
+        inputs.add_keyboard_event(press(KeyCode::Escape));
+        let recording = inputs.stop_recording().unwrap();
+        assert!(!inputs.is_recording());
+        assert_eq!(4, recording.frames());
+
+        let path = std::env::temp_dir().join(format!("scion_recording_{}.json", std::process::id()));
+        recording.save(&path).unwrap();
+        let recording = InputRecording::load(&path).unwrap();
+        let _r = fs::remove_file(path);
+        assert_eq!(42, recording.seed());
+
+        let mut replayed = InputsController::default();
+        replayed.replay(recording);
+        let mut random = Random::new(7);
+        replayed.sync_random(&mut random);
+        assert_eq!(42, random.seed());
+        let mut frames = Vec::new();
+        let mut durations = Vec::new();
+        for _ in 0..4 {
+            replayed.add_keyboard_event(press(KeyCode::Z));
+            durations.push(replayed.frame_duration(millis(1)));
+            replayed.start_frame();
+            frames.push((replayed.all_pressed_events(), replayed.mouse_xy()));
+            replayed.reset_inputs();
+        }
+        assert_eq!(vec![millis(16), millis(20), millis(33), millis(16)], durations);
+        assert_eq!((vec![], (0., 0.)), frames[0]);
+        assert_eq!((vec![Input::Key(KeyCode::Space)], (10., 20.)), frames[1]);
+        assert_eq!((vec![], (10., 20.)), frames[2]);
+        assert_eq!((vec![Input::Mouse(MouseButton::Left)], (10., 20.)), frames[3]);
+        assert!(!replayed.is_replaying());
+        assert_eq!(millis(1), replayed.frame_duration(millis(1)));
+        replayed.add_keyboard_event(press(KeyCode::Z));
+        assert_eq!(vec![Input::Key(KeyCode::Z)], replayed.all_pressed_events());
+    }
+}
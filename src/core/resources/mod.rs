@@ -1,5 +1,6 @@
 pub mod asset_manager;
 pub mod audio;
+pub mod commands;
 pub mod events;
 pub mod inputs;
 pub mod time;
@@ -7,3 +8,5 @@ pub mod window;
 pub mod font_atlas;
 pub mod focus_manager;
 pub mod global_storage;
+pub mod random;
+pub mod save_manager;
@@ -0,0 +1,269 @@
+//! Seeded random numbers, shared by the engine and the game to keep replays and tests deterministic.
+
+use std::collections::HashMap;
+use std::ops::{Range, RangeInclusive};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Name of the stream used by the helpers of [`Random`], for the numbers changing the game outcome
+pub const GAMEPLAY_STREAM: &str = "gameplay";
+/// Name of the stream to use for the numbers that don't change the game outcome, like particles or screen shakes
+pub const COSMETIC_STREAM: &str = "cosmetic";
+
+/// `Random` is the resource providing seeded random numbers. The seed comes from the `ScionConfig`, or from the
+/// current time if none is configured, and can be changed at runtime with [`Random::reseed`].
+///
+/// Numbers are drawn from independent named streams: drawing from a stream never changes the numbers of the others,
+/// so cosmetic effects can't break the reproduction of a session. The helpers of `Random` use the [`GAMEPLAY_STREAM`].
+/// ```
+/// use scion::core::resources::random::{Random, COSMETIC_STREAM};
+///
+/// let mut random = Random::new(42);
+/// let damage = random.range(5..=10);
+/// let color = *random.stream(COSMETIC_STREAM).pick(&["red", "green", "blue"]).unwrap();
+/// assert!((5..=10).contains(&damage));
+/// ```
+pub struct Random {
+    seed: u64,
+    streams: HashMap<String, RandomStream>,
+}
+
+impl Default for Random {
+    fn default() -> Self {
+        let seed = SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_nanos() as u64).unwrap_or_default();
+        Self::new(seed)
+    }
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Self {
+        Self { seed, streams: HashMap::new() }
+    }
+
+    /// Seed of the streams
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Changes the seed, restarting every stream
+    pub fn reseed(&mut self, seed: u64) {
+        self.seed = seed;
+        self.streams.clear();
+    }
+
+    /// Returns the stream `name`, created from the seed and the name the first time it is used
+    pub fn stream(&mut self, name: &str) -> &mut RandomStream {
+        let seed = self.seed;
+        self.streams.entry(name.to_string()).or_insert_with(|| RandomStream::new(stream_seed(seed, name)))
+    }
+
+    /// Random number within `range`, from the gameplay stream
+    pub fn range<R: RandomRange>(&mut self, range: R) -> R::Output {
+        self.stream(GAMEPLAY_STREAM).range(range)
+    }
+
+    /// Returns true with a probability of `probability`, from the gameplay stream
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.stream(GAMEPLAY_STREAM).chance(probability)
+    }
+
+    /// Random element of `items`, from the gameplay stream
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        self.stream(GAMEPLAY_STREAM).pick(items)
+    }
+
+    /// Random element of `items` chosen according to its weight, from the gameplay stream
+    pub fn weighted<'a, T>(&mut self, items: &'a [(T, f64)]) -> Option<&'a T> {
+        self.stream(GAMEPLAY_STREAM).weighted(items)
+    }
+
+    /// Shuffles `items`, from the gameplay stream
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        self.stream(GAMEPLAY_STREAM).shuffle(items)
+    }
+}
+
+/// A sequence of random numbers, generated with the SplitMix64 algorithm
+pub struct RandomStream {
+    state: u64,
+}
+
+impl RandomStream {
+    fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        mix(self.state)
+    }
+
+    /// Random number between 0 (included) and 1 (excluded)
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Random number within `range`. Panics if the range is empty
+    pub fn range<R: RandomRange>(&mut self, range: R) -> R::Output {
+        range.sample(self)
+    }
+
+    /// Returns true with a probability of `probability`
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.next_f64() < probability
+    }
+
+    /// Random element of `items`, None if `items` is empty
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.below(items.len() as u64) as usize)
+    }
+
+    /// Random element of `items` chosen according to its weight. Elements with a negative or zero weight are never
+    /// chosen. None if no element has a positive weight
+    pub fn weighted<'a, T>(&mut self, items: &'a [(T, f64)]) -> Option<&'a T> {
+        let total: f64 = items.iter().map(|(_, weight)| weight.max(0.)).sum();
+        if total <= 0. {
+            return None;
+        }
+        let mut target = self.next_f64() * total;
+        let mut chosen = None;
+        for (item, weight) in items.iter().filter(|(_, weight)| *weight > 0.) {
+            chosen = Some(item);
+            if target < *weight {
+                break;
+            }
+            target -= weight;
+        }
+        chosen
+    }
+
+    /// Shuffles `items` using the Fisher-Yates algorithm
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// Random number between 0 (included) and `bound` (excluded)
+    fn below(&mut self, bound: u64) -> u64 {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+}
+
+/// Range types usable with [`RandomStream::range`]
+pub trait RandomRange {
+    type Output;
+    fn sample(self, stream: &mut RandomStream) -> Self::Output;
+}
+
+macro_rules! impl_integer_range {
+    ($($t:ty),*) => {
+        $(
+            impl RandomRange for Range<$t> {
+                type Output = $t;
+                fn sample(self, stream: &mut RandomStream) -> $t {
+                    assert!(self.start < self.end, "Impossible to pick a random number in an empty range");
+                    let span = (self.end as i128 - self.start as i128) as u64;
+                    (self.start as i128 + stream.below(span) as i128) as $t
+                }
+            }
+
+            impl RandomRange for RangeInclusive<$t> {
+                type Output = $t;
+                fn sample(self, stream: &mut RandomStream) -> $t {
+                    let (start, end) = self.into_inner();
+                    assert!(start <= end, "Impossible to pick a random number in an empty range");
+                    let span = (end as i128 - start as i128) as u64;
+                    let offset = if span == u64::MAX { stream.next_u64() } else { stream.below(span + 1) };
+                    (start as i128 + offset as i128) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_range!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_float_range {
+    ($($t:ty),*) => {
+        $(
+            impl RandomRange for Range<$t> {
+                type Output = $t;
+                fn sample(self, stream: &mut RandomStream) -> $t {
+                    assert!(self.start < self.end, "Impossible to pick a random number in an empty range");
+                    let value = self.start + (self.end - self.start) * stream.next_f64() as $t;
+                    // Rounding can reach the excluded end
+                    if value < self.end { value } else { self.start }
+                }
+            }
+        )*
+    };
+}
+
+impl_float_range!(f32, f64);
+
+/// Seed of the stream `name`, so that each stream gets its own sequence
+fn stream_seed(seed: u64, name: &str) -> u64 {
+    // FNV-1a hash of the name
+    let name_hash = name.bytes().fold(0xCBF2_9CE4_8422_2325_u64, |hash, byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01B3));
+    mix(seed ^ name_hash)
+}
+
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn streams_are_seeded_and_independent_test() {
+        let mut first = Random::new(7);
+        let mut second = Random::new(7);
+        let gameplay: Vec<u64> = (0..5).map(|_| first.stream(GAMEPLAY_STREAM).next_u64()).collect();
+        (0..10).for_each(|_| {
+            second.stream(COSMETIC_STREAM).next_u64();
+        });
+        let replayed: Vec<u64> = (0..5).map(|_| second.stream(GAMEPLAY_STREAM).next_u64()).collect();
+        assert_eq!(gameplay, replayed);
+        assert_ne!(first.stream(GAMEPLAY_STREAM).next_u64(), first.stream(COSMETIC_STREAM).next_u64());
+
+        first.reseed(7);
+        assert_eq!(gameplay[0], first.stream(GAMEPLAY_STREAM).next_u64());
+        assert_ne!(gameplay[0], Random::new(8).stream(GAMEPLAY_STREAM).next_u64());
+    }
+
+    #[test]
+    fn helpers_test() {
+        let mut random = Random::new(3);
+        for _ in 0..1000 {
+            assert!((-3..2).contains(&random.range(-3..2)));
+            assert!((250..=255).contains(&random.range(250_u8..=255)));
+            let float = random.range(0.5_f32..1.);
+            assert!((0.5..1.).contains(&float));
+        }
+        assert_eq!(u64::MAX, random.range(u64::MAX..=u64::MAX));
+        assert!(random.chance(1.));
+        assert!(!random.chance(0.));
+
+        assert_eq!(None, random.pick::<u8>(&[]));
+        assert_eq!(Some(&4), random.pick(&[4]));
+        assert_eq!(None, random.weighted(&[("never", 0.)]));
+        for _ in 0..100 {
+            assert_eq!(Some(&"always"), random.weighted(&[("never", 0.), ("always", 2.), ("negative", -1.)]));
+        }
+
+        let mut items: Vec<usize> = (0..20).collect();
+        random.shuffle(&mut items);
+        assert_ne!((0..20).collect::<Vec<usize>>(), items);
+        items.sort();
+        assert_eq!((0..20).collect::<Vec<usize>>(), items);
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::snapshot::{SnapshotError, WorldSnapshot};
+use crate::core::state::GameState;
+use crate::core::world::GameData;
+use crate::utils::file::app_data_path;
+
+const SAVE_EXTENSION: &str = "json";
+const TEMPORARY_EXTENSION: &str = "json.tmp";
+
+/// Function upgrading the content of a save file from a version to the next one
+pub type Migration = fn(&mut Value);
+
+/// `SaveError` represents the different errors that saving or loading a slot can return
+#[derive(Debug)]
+pub enum SaveError {
+    /// Slot names can only contain alphanumeric characters, '-' and '_'
+    InvalidSlotName(String),
+    SlotDoesNotExist(String),
+    /// The save file can't be read or written. Contains the io error message
+    IoError(String),
+    /// The save file or a component can't be (de)serialized. Contains the serialization error message
+    SerializationError(String),
+    /// No migration is registered to upgrade saves from this version
+    MissingMigration(u32),
+    /// The save has been written by a newer version of the game
+    UnsupportedVersion(u32),
+}
+
+/// Content of a save file
+#[derive(Serialize, Deserialize)]
+struct SaveFile {
+    version: u32,
+    game_state: Value,
+    /// Registered components of the saved entities
+    entities: WorldSnapshot,
+}
+
+/// `SaveManager` is the resource used to save the game into named slots and load it back.
+/// A save contains the `GameState` and the entities holding the components registered into the
+/// [`crate::core::snapshot::ComponentRegistry`], the engine ones included, except the session only ones.
+/// [`crate::core::resources::asset_manager::AssetRef`]s are only valid during the session and can't be saved.
+/// Slots are stored in the `saves` folder of the application data directory, derived from the app
+/// name of the `ScionConfig`.
+/// ```no_run
+/// use serde::{Deserialize, Serialize};
+/// use scion::core::resources::save_manager::SaveManager;
+/// use scion::core::world::GameData;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Player { lives: usize }
+///
+/// fn configure(data: &mut GameData) {
+///     let mut saves = data.save_manager();
+///     saves.set_version(2);
+///     // Version 1 stored the lives in the game state
+///     saves.add_migration(1, |save| save["game_state"]["values"]["lives"] = 3.into());
+///     data.component_registry().register::<Player>("Player");
+/// }
+///
+/// fn save_and_reload(data: &mut GameData) {
+///     SaveManager::save(data, "slot_1").unwrap();
+///     SaveManager::load(data, "slot_1").unwrap();
+/// }
+/// ```
+pub struct SaveManager {
+    directory: PathBuf,
+    version: u32,
+    /// Migrations by version they upgrade from
+    migrations: HashMap<u32, Migration>,
+}
+
+impl SaveManager {
+    pub(crate) fn new(app_name: &str) -> Self {
+        Self::with_directory(app_data_path(app_name).join("saves"))
+    }
+
+    pub(crate) fn with_directory(directory: PathBuf) -> Self {
+        Self { directory, version: 1, migrations: HashMap::new() }
+    }
+
+    /// Directory where the slots are stored
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Changes the directory where the slots are stored
+    pub fn set_directory(&mut self, directory: PathBuf) {
+        self.directory = directory;
+    }
+
+    /// Version written into the saves. Default is 1
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    pub fn set_version(&mut self, version: u32) {
+        self.version = version;
+    }
+
+    /// Registers `migration`, upgrading the content of saves written with `from_version` to `from_version + 1`.
+    /// Loading a slot applies the migrations one after the other until the current version is reached.
+    pub fn add_migration(&mut self, from_version: u32, migration: Migration) {
+        self.migrations.insert(from_version, migration);
+    }
+
+    /// Names of the existing slots, sorted
+    pub fn slots(&self) -> Vec<String> {
+        let mut slots: Vec<String> = fs::read_dir(&self.directory)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|extension| extension == SAVE_EXTENSION))
+                    .filter_map(|path| path.file_stem().and_then(|stem| stem.to_str()).map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        slots.sort();
+        slots
+    }
+
+    /// Returns whether or not the slot `slot` exists
+    pub fn exists(&self, slot: &str) -> bool {
+        self.slot_path(slot).is_ok_and(|path| path.exists())
+    }
+
+    /// Deletes the slot `slot`
+    pub fn delete(&mut self, slot: &str) -> Result<(), SaveError> {
+        let path = self.slot_path(slot)?;
+        if !path.exists() {
+            return Err(SaveError::SlotDoesNotExist(slot.to_string()));
+        }
+        fs::remove_file(path).map_err(|e| SaveError::IoError(e.to_string()))
+    }
+
+    /// Saves the game state and the entities holding registered components into `slot`, replacing its previous content.
+    /// The save is written into a temporary file first, then moved to replace the slot, so that
+    /// a crash during the save never leaves a corrupted slot.
+    pub fn save(data: &GameData, slot: &str) -> Result<(), SaveError> {
+        let manager = data.save_manager();
+        let path = manager.slot_path(slot)?;
+        let save_file = SaveFile {
+            version: manager.version,
+            game_state: serde_json::to_value(&*data.game_state()).map_err(serialization_error)?,
+            entities: data.component_registry().without_session_only().capture(data).map_err(snapshot_error)?,
+        };
+        let content = serde_json::to_vec_pretty(&save_file).map_err(serialization_error)?;
+        write_atomically(&manager.directory, &path, &content)?;
+        info!("Game saved into slot '{}'", slot);
+        Ok(())
+    }
+
+    /// Loads `slot`, migrating it to the current version if needed. The game state is replaced by
+    /// the saved one, and the entities holding registered components are replaced by the saved entities.
+    pub fn load(data: &mut GameData, slot: &str) -> Result<(), SaveError> {
+        let (save_file, components) = {
+            let manager = data.save_manager();
+            let path = manager.slot_path(slot)?;
+            if !path.exists() {
+                return Err(SaveError::SlotDoesNotExist(slot.to_string()));
+            }
+            let content = fs::read(&path).map_err(|e| SaveError::IoError(e.to_string()))?;
+            let mut save: Value = serde_json::from_slice(&content).map_err(serialization_error)?;
+            manager.migrate(&mut save)?;
+            let save_file: SaveFile = serde_json::from_value(save).map_err(serialization_error)?;
+            (save_file, data.component_registry().without_session_only())
+        };
+
+        // Everything is deserialized before replacing the world, so that an invalid save leaves it untouched
+        let game_state: GameState = serde_json::from_value(save_file.game_state).map_err(serialization_error)?;
+        components.restore(&save_file.entities, data).map_err(snapshot_error)?;
+        data.game_state_mut().restore(game_state);
+        info!("Game loaded from slot '{}'", slot);
+        Ok(())
+    }
+
+    fn migrate(&self, save: &mut Value) -> Result<(), SaveError> {
+        let mut version = save
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| SaveError::SerializationError("The save has no version".to_string()))? as u32;
+        if version > self.version {
+            return Err(SaveError::UnsupportedVersion(version));
+        }
+        while version < self.version {
+            let migration = self.migrations.get(&version).ok_or(SaveError::MissingMigration(version))?;
+            migration(save);
+            version += 1;
+            debug!("Save migrated to version {}", version);
+        }
+        save["version"] = Value::from(version);
+        Ok(())
+    }
+
+    fn slot_path(&self, slot: &str) -> Result<PathBuf, SaveError> {
+        if slot.is_empty() || !slot.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+            return Err(SaveError::InvalidSlotName(slot.to_string()));
+        }
+        Ok(self.directory.join(format!("{}.{}", slot, SAVE_EXTENSION)))
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> SaveError {
+    SaveError::SerializationError(e.to_string())
+}
+
+fn snapshot_error(e: SnapshotError) -> SaveError {
+    match e {
+        SnapshotError::SerializationError(message) => SaveError::SerializationError(message),
+        SnapshotError::UnregisteredComponent(name) => {
+            SaveError::SerializationError(format!("The component '{}' is not registered", name))
+        }
+    }
+}
+
+/// Writes `content` into a temporary file, flushed to the disk, then renames it to `path`
+fn write_atomically(directory: &Path, path: &Path, content: &[u8]) -> Result<(), SaveError> {
+    let io_error = |e: std::io::Error| SaveError::IoError(e.to_string());
+    fs::create_dir_all(directory).map_err(io_error)?;
+    let temporary_path = path.with_extension(TEMPORARY_EXTENSION);
+    {
+        let mut file = fs::File::create(&temporary_path).map_err(io_error)?;
+        file.write_all(content).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+    }
+    fs::rename(&temporary_path, path).map_err(io_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::snapshot::ComponentRegistry;
+    use crate::core::world::World;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Player {
+        lives: usize,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Position(f32, f32);
+
+    fn game_data(test_name: &str) -> (GameData, PathBuf) {
+        let directory = std::env::temp_dir().join(format!("scion_saves_{}_{}", test_name, std::process::id()));
+        let _r = fs::remove_dir_all(&directory);
+        let mut data = GameData::default();
+        data.insert_resource(GameState::default());
+        data.insert_resource(SaveManager::with_directory(directory.clone()));
+        data.insert_resource(ComponentRegistry::default());
+        data.component_registry().register::<Player>("Player");
+        data.component_registry().register::<Position>("Position");
+        (data, directory)
+    }
+
+    #[test]
+    fn save_and_load_test() {
+        let (mut data, directory) = game_data("roundtrip");
+        data.game_state_mut().set_int("score", 42);
+        data.push((Player { lives: 3 }, Position(1., 2.)));
+        data.push((Position(5., 5.), 1_u8));
+        SaveManager::save(&data, "slot_1").unwrap();
+        assert_eq!(vec!["slot_1".to_string()], data.save_manager().slots());
+        assert!(!directory.join("slot_1.json.tmp").exists());
+
+        data.game_state_mut().set_int("score", 0);
+        data.game_state_mut().set_bool("unsaved", true);
+        data.push((Player { lives: 1 },));
+        SaveManager::load(&mut data, "slot_1").unwrap();
+
+        assert_eq!(Some(42), data.game_state().get_int("score"));
+        assert!(!data.game_state().get_bool("unsaved"));
+        let players: Vec<(usize, Option<f32>)> = data
+            .query::<(&Player, Option<&Position>)>()
+            .iter()
+            .map(|(_, (player, position))| (player.lives, position.map(|position| position.0)))
+            .collect();
+        assert_eq!(vec![(3, Some(1.))], players);
+        assert_eq!(2, data.query::<&Position>().iter().count());
+        // Components that are not registered are not saved
+        assert_eq!(0, data.query::<&u8>().iter().count());
+
+        data.save_manager().delete("slot_1").unwrap();
+        assert!(matches!(SaveManager::load(&mut data, "slot_1"), Err(SaveError::SlotDoesNotExist(_))));
+        assert!(matches!(SaveManager::save(&data, "../slot"), Err(SaveError::InvalidSlotName(_))));
+        let _r = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn session_only_components_are_not_saved_test() {
+        let (mut data, directory) = game_data("session_only");
+        data.component_registry().register_session_only::<u16>("u16");
+        data.push((Player { lives: 3 }, 7_u16));
+        assert_eq!(1, WorldSnapshot::capture(&data).unwrap().len());
+        SaveManager::save(&data, "slot").unwrap();
+
+        let session_entity = data.push((8_u16,));
+        SaveManager::load(&mut data, "slot").unwrap();
+        assert_eq!(1, data.query::<&Player>().iter().count());
+        assert_eq!(vec![8], data.query::<&u16>().iter().map(|(_, value)| *value).collect::<Vec<u16>>());
+        assert!(data.contains(session_entity));
+        let _r = fs::remove_dir_all(directory);
+    }
+
+    #[test]
+    fn migrations_test() {
+        let (mut data, directory) = game_data("migrations");
+        data.push((Player { lives: 3 },));
+        SaveManager::save(&data, "slot").unwrap();
+
+        data.save_manager().set_version(3);
+        assert!(matches!(SaveManager::load(&mut data, "slot"), Err(SaveError::MissingMigration(1))));
+        data.save_manager().add_migration(1, |save| {
+            save["entities"][0]["components"]["Player"]["lives"] = Value::from(5);
+        });
+        data.save_manager().add_migration(2, |save| {
+            save["game_state"]["values"]["migrated"] = Value::from(true);
+        });
+        SaveManager::load(&mut data, "slot").unwrap();
+        assert_eq!(&Player { lives: 5 }, data.query::<&Player>().iter().next().unwrap().1);
+        assert_eq!(Some(true), data.game_state().get::<bool>("migrated"));
+
+        SaveManager::save(&data, "slot").unwrap();
+        data.save_manager().set_version(2);
+        assert!(matches!(SaveManager::load(&mut data, "slot"), Err(SaveError::UnsupportedVersion(3))));
+
+        // A wrong migration of the game state leaves the world untouched
+        data.save_manager().set_version(4);
+        data.save_manager().add_migration(3, |save| save["game_state"] = Value::from(1));
+        let player = data.query::<&Player>().iter().next().unwrap().0;
+        assert!(matches!(SaveManager::load(&mut data, "slot"), Err(SaveError::SerializationError(_))));
+        assert!(data.contains(player));
+        let _r = fs::remove_dir_all(directory);
+    }
+}
@@ -11,43 +11,146 @@ mod time {
     use std::time::{Duration, Instant};
 
     /// ['Time'] is a resource dedicated to compute the time durations between frames and keep a track of
-    /// frame numbers
+    /// frame numbers.
+    ///
+    /// The elapsed time is multiplied by the time scale, to slow down, speed up or pause the game : the scaled
+    /// durations drive the timers, the animations, the scheduled events and the fixed updates. The real durations
+    /// stay available with [`Time::unscaled_delta_duration`], for the menus or any UI that must keep running.
     pub struct Time {
         delta_duration: Duration,
+        unscaled_delta_duration: Duration,
+        fixed_delta_duration: Duration,
+        total_duration: Duration,
+        unscaled_total_duration: Duration,
         frame_number: u64,
         measure_start: Instant,
+        time_scale: f32,
+        paused: bool,
     }
 
     impl Default for Time {
         fn default() -> Self {
             Self {
                 delta_duration: Default::default(),
+                unscaled_delta_duration: Default::default(),
+                fixed_delta_duration: Duration::from_secs(1) / 60,
+                total_duration: Duration::ZERO,
+                unscaled_total_duration: Duration::ZERO,
                 frame_number: 0,
                 measure_start: Instant::now(),
+                time_scale: 1.,
+                paused: false,
             }
         }
     }
 
     impl Time {
-        /// finish the last frame and return its duration
-        pub(crate) fn frame(&mut self) -> Duration {
-            self.frame_number += 1;
-            self.delta_duration = self.measure_start.elapsed();
+        /// finish the last frame and return its real duration, to give to [`Time::advance`]
+        pub(crate) fn measure(&mut self) -> Duration {
+            let elapsed = self.measure_start.elapsed();
             self.measure_start = Instant::now();
+            elapsed
+        }
+
+        /// finish the last frame using the given real duration and return its scaled duration
+        pub(crate) fn advance(&mut self, frame_duration: Duration) -> Duration {
+            self.frame_number += 1;
+            self.unscaled_delta_duration = frame_duration;
+            self.delta_duration = scale_duration(frame_duration, self.effective_time_scale());
+            self.total_duration += self.delta_duration;
+            self.unscaled_total_duration += frame_duration;
             self.delta_duration
         }
 
-        /// Returns the duration of the last executed frame
+        /// Returns the duration of the last executed frame, multiplied by the time scale
         pub fn delta_duration(&self) -> Duration {
             self.delta_duration
         }
+
+        /// Returns the real duration of the last executed frame, ignoring the time scale and the pause
+        pub fn unscaled_delta_duration(&self) -> Duration {
+            self.unscaled_delta_duration
+        }
+
+        /// Returns the total scaled duration of the executed frames since the game started
+        pub fn total_duration(&self) -> Duration {
+            self.total_duration
+        }
+
+        /// Returns the total real duration of the executed frames since the game started, ignoring the time scale
+        /// and the pause
+        pub fn unscaled_total_duration(&self) -> Duration {
+            self.unscaled_total_duration
+        }
+
+        /// Returns the number of the current frame
+        pub fn frame_number(&self) -> u64 {
+            self.frame_number
+        }
+
+        /// Returns the duration simulated by each fixed update
+        pub fn fixed_delta_duration(&self) -> Duration {
+            self.fixed_delta_duration
+        }
+
+        /// Returns the time scale, 1 being the real time
+        pub fn time_scale(&self) -> f32 {
+            self.time_scale
+        }
+
+        /// Sets the time scale : 0.5 runs the game twice slower, 2 twice faster, and 0 pauses it
+        pub fn set_time_scale(&mut self, time_scale: f32) {
+            assert!(time_scale >= 0. && time_scale.is_finite(), "The time scale must be a positive number");
+            self.time_scale = time_scale;
+        }
+
+        /// Pauses the game, keeping the time scale to apply when resuming
+        pub fn pause(&mut self) {
+            self.paused = true;
+        }
+
+        /// Resumes the game paused with [`Time::pause`]
+        pub fn resume(&mut self) {
+            self.paused = false;
+        }
+
+        /// Returns whether the game time is stopped, either paused or scaled to zero
+        pub fn is_paused(&self) -> bool {
+            self.effective_time_scale() == 0.
+        }
+
+        /// Time scale applied to the next frames
+        pub(crate) fn effective_time_scale(&self) -> f32 {
+            if self.paused {
+                0.
+            } else {
+                self.time_scale
+            }
+        }
+
+        pub(crate) fn set_fixed_update_rate(&mut self, fixed_update_rate: u32) {
+            assert!(fixed_update_rate > 0, "The fixed update rate must be greater than zero");
+            self.fixed_delta_duration = Duration::from_secs(1) / fixed_update_rate;
+        }
+    }
+
+    /// Multiplies `duration` by `time_scale`, keeping unscaled durations exact
+    pub(crate) fn scale_duration(duration: Duration, time_scale: f32) -> Duration {
+        if time_scale == 1. {
+            duration
+        } else {
+            duration.mul_f64(time_scale as f64)
+        }
     }
 }
 
 mod timer {
     use std::{collections::HashMap, time::Duration};
 
+    use hecs::Entity;
+
     use crate::core::resources::time::Error;
+    use crate::core::scene::SceneId;
 
     /// Different types of timer that car be used
     pub enum TimerType {
@@ -59,6 +162,25 @@ mod timer {
         Cyclic,
     }
 
+    /// A timer, either managed by name in the [`Timers`] resource, or added as a component to an entity.
+    /// Timer components are ticked by the engine, which adds a [`Finished`] marker to their entity during the frame
+    /// they end or complete a cycle. A [`TimerFinished`] event is also published into the typed channel of the `Events`
+    /// for both kinds of timers.
+    /// ```no_run
+    /// use scion::core::resources::time::{Finished, Timer, TimerType};
+    /// use scion::core::world::{GameData, World};
+    ///
+    /// struct Cannon;
+    ///
+    /// fn cannon_system(data: &mut GameData) {
+    ///     for (_, _) in data.query::<(&Cannon, &Finished)>().iter() {
+    ///         // Shoot, the cooldown timer will start over
+    ///     }
+    /// }
+    ///
+    /// # let mut data = GameData::default();
+    /// data.push((Cannon, Timer::new(1.5, TimerType::Cyclic)));
+    /// ```
     pub struct Timer {
         /// Type of the current timer
         timer_type: TimerType,
@@ -72,11 +194,15 @@ mod timer {
         dirty: bool,
         /// Total cycles since last cycle fn call
         current_elapsed_cycles: usize,
+        /// Scene that created this timer, and whose stop deletes it
+        owner: Option<SceneId>,
+        /// Whether this timer is used by the engine itself, in which case its end isn't notified
+        internal: bool,
     }
 
     impl Timer {
-        /// Creates a new timer, only to be used internally.
-        pub(crate) fn new(total_duration: f32, timer_type: TimerType) -> Self {
+        /// Creates a new running timer of `total_duration` seconds. Use [`Timers::add_timer`] for named timers.
+        pub fn new(total_duration: f32, timer_type: TimerType) -> Self {
             Self {
                 timer_type,
                 running: true,
@@ -84,9 +210,16 @@ mod timer {
                 total_duration,
                 dirty: false,
                 current_elapsed_cycles: 0,
+                owner: None,
+                internal: false,
             }
         }
 
+        /// Keeps this timer when the scene that created it stops
+        pub fn set_persistent(&mut self) {
+            self.owner = None;
+        }
+
         /// Adds the duration to the current timer and return whether or not the timer has ended or
         /// done a cycle
         pub fn add_delta_duration(&mut self, delta_duration: f32) -> bool {
@@ -139,6 +272,11 @@ mod timer {
             self.total_duration = new_cycle;
         }
 
+        /// Returns whether or not the timer ended or completed a cycle during its last update
+        pub fn just_finished(&self) -> bool {
+            self.dirty
+        }
+
         /// Returns the number of cycles elapsed since the last call of this fn
         pub fn cycle(&mut self) -> usize {
             let res = self.current_elapsed_cycles;
@@ -147,11 +285,26 @@ mod timer {
         }
     }
 
+    /// Marker component added to an entity during the frame its [`Timer`] component ends or completes a cycle
+    #[derive(Debug)]
+    pub struct Finished;
+
+    /// Event published into the typed channel of the `Events` each time a timer ends or completes a cycle
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum TimerFinished {
+        /// A timer of the [`Timers`] resource, identified by its name
+        Named(String),
+        /// The [`Timer`] component of the entity
+        Entity(Entity),
+    }
+
     /// Timers is a convenience resource provided by `Scion`
     /// in order to help users to create timers in their systems/layers
     #[derive(Default)]
     pub struct Timers {
         timers: HashMap<String, Timer>,
+        /// Scene owning the timers created now
+        pub(crate) scene_owner: Option<SceneId>,
     }
 
     impl Timers {
@@ -165,10 +318,19 @@ mod timer {
             if self.timers.contains_key(name) {
                 return Err(Error::TimerAlreadyExists);
             }
-            self.timers.insert(name.to_string(), Timer::new(duration_in_second, timer_type));
+            let mut timer = Timer::new(duration_in_second, timer_type);
+            timer.owner = self.scene_owner;
+            self.timers.insert(name.to_string(), timer);
             Ok(self.timers.get_mut(name).expect("Missing the timer we just inserted..."))
         }
 
+        /// Create and adds a timer used by the engine itself, whose end doesn't publish any event
+        pub(crate) fn add_internal_timer(&mut self, name: &str, timer_type: TimerType, duration_in_second: f32) {
+            let mut timer = Timer::new(duration_in_second, timer_type);
+            timer.internal = true;
+            self.timers.insert(name.to_string(), timer);
+        }
+
         /// Delete a timer from the list of known timers
         pub fn delete_timer(&mut self, name: &str) -> Result<(), Error> {
             if self.timers.contains_key(name) {
@@ -189,18 +351,63 @@ mod timer {
             self.timers.get_mut(name).ok_or(Error::TimerDoesNotExist)
         }
 
-        pub(crate) fn add_delta_duration(&mut self, delta_duration: Duration) {
+        /// Deletes the timers created by `scene`, unless they were made persistent
+        pub(crate) fn delete_scene_timers(&mut self, scene: SceneId) {
+            self.timers.retain(|_, timer| timer.owner != Some(scene));
+        }
+
+        /// Adds the scaled duration to every timer, or the unscaled one to the internal timers so that they keep
+        /// running while the game is paused, and returns the sorted names of the non internal timers that ended or
+        /// completed a cycle
+        pub(crate) fn add_delta_duration(
+            &mut self,
+            delta_duration: Duration,
+            unscaled_delta_duration: Duration,
+        ) -> Vec<String> {
             let delta = delta_duration.as_secs_f32();
-            self.timers.values_mut().for_each(|timer| {
-                timer.add_delta_duration(delta);
-            })
+            let unscaled_delta = unscaled_delta_duration.as_secs_f32();
+            let mut finished: Vec<String> = self
+                .timers
+                .iter_mut()
+                .filter_map(|(name, timer)| {
+                    if timer.internal {
+                        timer.add_delta_duration(unscaled_delta);
+                        None
+                    } else {
+                        timer.add_delta_duration(delta).then(|| name.clone())
+                    }
+                })
+                .collect();
+            finished.sort();
+            finished
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::core::resources::time::{Timers, TimerType};
+    use std::time::Duration;
+
+    use crate::core::resources::time::{Time, Timers, TimerType};
+
+    #[test]
+    fn time_scale_test() {
+        let mut time = Time::default();
+        time.set_time_scale(0.5);
+        assert_eq!(Duration::from_millis(50), time.advance(Duration::from_millis(100)));
+        assert_eq!(Duration::from_millis(100), time.unscaled_delta_duration());
+
+        time.pause();
+        assert!(time.is_paused());
+        assert_eq!(Duration::ZERO, time.advance(Duration::from_millis(100)));
+        time.resume();
+        assert_eq!(0.5, time.time_scale());
+        time.set_time_scale(2.);
+        assert_eq!(Duration::from_millis(200), time.advance(Duration::from_millis(100)));
+        assert_eq!(Duration::from_millis(250), time.total_duration());
+        assert_eq!(Duration::from_millis(300), time.unscaled_total_duration());
+        assert_eq!(3, time.frame_number());
+    }
 
     #[test]
     fn add_timer_test() {
@@ -228,4 +435,20 @@ mod tests {
         assert_eq!(0.5, timer.elapsed());
         assert!(!timer.ended());
     }
+
+    #[test]
+    fn finished_timers_test() {
+        let mut timers = Timers::default();
+        ["d", "a", "c", "b"].iter().for_each(|name| {
+            let _r = timers.add_timer(name, TimerType::Manual, 0.5);
+        });
+        timers.add_internal_timer("internal", TimerType::Cyclic, 0.5);
+        let second = Duration::from_secs(1);
+        assert_eq!(vec!["a", "b", "c", "d"], timers.add_delta_duration(second, second));
+        assert_eq!(2, timers.get_timer("internal").unwrap().cycle());
+
+        // Paused game
+        assert!(timers.add_delta_duration(Duration::ZERO, second).is_empty());
+        assert_eq!(2, timers.get_timer("internal").unwrap().cycle());
+    }
 }
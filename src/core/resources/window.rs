@@ -18,17 +18,55 @@ impl Default for FutureSettings{
 /// [`Window`] is a Resource dedicated to have an access control over the current window.
 /// Its size is immediately updated when the window resize event happens.
 /// new_cursor is set at the end of the current frame.
+/// It is also used to quit the game, see [`Window::exit`].
 #[derive(Default, Debug, Copy, Clone)]
 pub struct Window {
     width: u32,
     height: u32,
     dpi: f64,
-    future_settings: FutureSettings
+    future_settings: FutureSettings,
+    exit_requested: bool,
+    close_intercepted: bool,
+    close_requested: bool,
 }
 
 impl Window {
     pub(crate) fn new(screen_size: (u32, u32), dpi: f64) -> Self {
-        Self { width: screen_size.0, height: screen_size.1, future_settings: Default::default(), dpi }
+        Self { width: screen_size.0, height: screen_size.1, future_settings: Default::default(), dpi, ..Default::default() }
+    }
+
+    /// Asks the game to quit at the end of the current frame. The current scene's `on_stop` and the
+    /// systems shutdown hooks will be called before the window is closed.
+    pub fn exit(&mut self) {
+        self.exit_requested = true;
+    }
+
+    /// Whether or not [`Window::exit`] has been called
+    pub fn is_exit_requested(&self) -> bool {
+        self.exit_requested
+    }
+
+    /// When `intercept` is true, the close button of the window doesn't quit the game anymore.
+    /// Instead, [`Window::close_requested`] is set during a frame, and the game is responsible for calling [`Window::exit`].
+    pub fn intercept_close(&mut self, intercept: bool) {
+        self.close_intercepted = intercept;
+    }
+
+    /// Whether or not the close button has been pressed during the current frame, while the close is intercepted
+    pub fn close_requested(&self) -> bool {
+        self.close_requested
+    }
+
+    pub(crate) fn request_close(&mut self) {
+        if self.close_intercepted {
+            self.close_requested = true;
+        } else {
+            self.exit_requested = true;
+        }
+    }
+
+    pub(crate) fn reset_close_request(&mut self) {
+        self.close_requested = false;
     }
 
     pub(crate) fn set_dimensions(&mut self, width: u32, height: u32) {
@@ -75,3 +113,23 @@ impl Window {
         &self.future_settings.dimensions
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn close_interception_test() {
+        let mut window = Window::new((10, 10), 1.);
+        window.intercept_close(true);
+        window.request_close();
+        assert!(window.close_requested());
+        assert!(!window.is_exit_requested());
+        window.reset_close_request();
+        assert!(!window.close_requested());
+
+        window.intercept_close(false);
+        window.request_close();
+        assert!(window.is_exit_requested());
+    }
+}
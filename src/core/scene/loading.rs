@@ -0,0 +1,284 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::mpsc::{channel, Receiver, TryRecvError};
+use std::thread;
+
+use image::DynamicImage;
+use log::{debug, error};
+
+use crate::core::resources::asset_manager::{AssetManager, AssetType};
+use crate::core::world::GameData;
+use crate::graphics::components::material::load_png;
+use crate::graphics::components::tiles::atlas::data::TilemapAtlas;
+use crate::graphics::components::tiles::atlas::importer::{try_import_tilemap, try_import_tileset};
+use crate::graphics::components::tiles::tileset::Tileset;
+use crate::utils::file::read_file;
+
+/// Asset needed by a scene, loaded on a background thread before its `on_start` (see [`crate::core::scene::Scene::assets`])
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum AssetRequest {
+    /// Tilemap registered in the `AssetManager` as `AssetType::Tilemap(name)`, loaded with its tilesets and their textures
+    Tilemap(String),
+    /// Tileset registered in the `AssetManager` as `AssetType::Tileset(name)`, loaded with its texture
+    Tileset(String),
+    /// Png texture located at the given path
+    Texture(String),
+    /// True type font located at the given path
+    Font(String),
+}
+
+/// `LoadingProgress` is the resource exposing the progress of the scene assets loading, to be displayed
+/// by the loading scene (see [`crate::ScionBuilder::with_loading_scene`])
+#[derive(Default, Debug)]
+pub struct LoadingProgress {
+    loading: bool,
+    loaded_assets: usize,
+    total_assets: usize,
+}
+
+impl LoadingProgress {
+    /// Whether or not assets are being loaded
+    pub fn is_loading(&self) -> bool {
+        self.loading
+    }
+
+    /// Number of assets already loaded
+    pub fn loaded_assets(&self) -> usize {
+        self.loaded_assets
+    }
+
+    /// Number of assets requested by the scene being loaded
+    pub fn total_assets(&self) -> usize {
+        self.total_assets
+    }
+
+    /// Ratio of loaded assets, between 0 and 1
+    pub fn progress(&self) -> f32 {
+        if self.total_assets == 0 {
+            return 1.;
+        }
+        self.loaded_assets as f32 / self.total_assets as f32
+    }
+
+    pub(crate) fn update(&mut self, loading: bool, loaded_assets: usize, total_assets: usize) {
+        self.loading = loading;
+        self.loaded_assets = loaded_assets;
+        self.total_assets = total_assets;
+    }
+}
+
+/// Asset request with the paths resolved from the `AssetManager`, ready to be sent to the loading thread
+enum AssetJob {
+    Tilemap { name: String, path: String, tilesets: HashMap<String, (String, String)> },
+    Tileset { atlas_path: String, texture_path: String },
+    Texture(String),
+    Font(String),
+}
+
+impl AssetJob {
+    fn resolve(request: AssetRequest, assets: &AssetManager) -> Option<AssetJob> {
+        let tileset_paths = |name: &str| {
+            assets
+                .find_atlas_path(&AssetType::Tileset(name.to_string()))
+                .zip(assets.find_texture_for_tileset(name))
+        };
+        match request {
+            AssetRequest::Tilemap(name) => {
+                let path = assets.find_atlas_path(&AssetType::Tilemap(name.to_string()))?;
+                let tilesets = assets
+                    .registered_tileset_names()
+                    .into_iter()
+                    .filter(|tileset| assets.retrieve_asset_ref_for_tileset_name(tileset).is_none())
+                    .filter_map(|tileset| tileset_paths(&tileset).map(|paths| (tileset, paths)))
+                    .collect();
+                Some(AssetJob::Tilemap { name, path, tilesets })
+            }
+            AssetRequest::Tileset(name) => {
+                let (atlas_path, texture_path) = tileset_paths(&name)?;
+                Some(AssetJob::Tileset { atlas_path, texture_path })
+            }
+            AssetRequest::Texture(path) => Some(AssetJob::Texture(path)),
+            AssetRequest::Font(path) => Some(AssetJob::Font(path)),
+        }
+    }
+
+    /// Loads the asset, on the loading thread. Failures are logged, letting the scene load the asset by itself.
+    fn load(self) -> Option<LoadedAsset> {
+        match self {
+            AssetJob::Tilemap { name, path, tilesets } => {
+                let tilemap = try_import_tilemap(&path).map_err(|e| error!("{:?}", e)).ok()?;
+                let loaded_tilesets = tilemap
+                    .tilesets
+                    .iter()
+                    .filter_map(|tileset| tilesets.get(&tileset.name))
+                    .filter_map(|(atlas_path, texture_path)| {
+                        AssetJob::Tileset { atlas_path: atlas_path.to_string(), texture_path: texture_path.to_string() }.load()
+                    })
+                    .collect();
+                Some(LoadedAsset::Tilemap(name, tilemap, loaded_tilesets))
+            }
+            AssetJob::Tileset { atlas_path, texture_path } => {
+                let atlas = try_import_tileset(&atlas_path).map_err(|e| error!("{:?}", e)).ok()?;
+                let texture = load_png(Path::new(&texture_path));
+                Some(LoadedAsset::Tileset(atlas.into_tileset(texture_path), texture))
+            }
+            AssetJob::Texture(path) => match load_png(Path::new(&path)) {
+                Some(image) => Some(LoadedAsset::Image(path, image)),
+                None => {
+                    error!("Impossible to preload texture {}", path);
+                    None
+                }
+            },
+            AssetJob::Font(path) => match read_file(Path::new(&path)) {
+                Ok(font_file) => Some(LoadedAsset::Font(path, font_file)),
+                Err(e) => {
+                    error!("Impossible to preload font {}: {:?}", path, e);
+                    None
+                }
+            },
+        }
+    }
+}
+
+enum LoadedAsset {
+    Tilemap(String, TilemapAtlas, Vec<LoadedAsset>),
+    Tileset(Tileset, Option<DynamicImage>),
+    Image(String, DynamicImage),
+    Font(String, Vec<u8>),
+}
+
+impl LoadedAsset {
+    fn install(self, data: &mut GameData) {
+        match self {
+            LoadedAsset::Tilemap(name, tilemap, tilesets) => {
+                tilesets.into_iter().for_each(|tileset| tileset.install(data));
+                data.assets_mut().add_preloaded_tilemap(name, tilemap);
+            }
+            LoadedAsset::Tileset(tileset, texture) => {
+                let mut assets = data.assets_mut();
+                if let Some(texture) = texture {
+                    assets.add_preloaded_image(tileset.texture.to_string(), texture);
+                }
+                assets.register_tileset(tileset);
+            }
+            LoadedAsset::Image(path, image) => data.assets_mut().add_preloaded_image(path, image),
+            LoadedAsset::Font(path, font_file) => data.font_atlas().add_font_file(path, font_file),
+        }
+    }
+}
+
+/// Loads the assets requested by a scene on a background thread
+pub(crate) struct AssetLoader {
+    receiver: Receiver<Option<LoadedAsset>>,
+    loaded: Vec<LoadedAsset>,
+    received: usize,
+    total: usize,
+}
+
+impl AssetLoader {
+    pub(crate) fn start(requests: Vec<AssetRequest>, data: &GameData) -> Self {
+        let jobs: Vec<AssetJob> = {
+            let assets = data.assets();
+            requests
+                .into_iter()
+                .filter_map(|request| {
+                    let job = AssetJob::resolve(request.clone(), &assets);
+                    if job.is_none() {
+                        error!("Impossible to preload {:?}, it is missing from the asset manager", request);
+                    }
+                    job
+                })
+                .collect()
+        };
+        debug!("Preloading {} scene assets", jobs.len());
+        let total = jobs.len();
+        let (sender, receiver) = channel();
+        let spawned = thread::Builder::new().name("scion-asset-loader".to_string()).spawn(move || {
+            for job in jobs {
+                if sender.send(job.load()).is_err() {
+                    break;
+                }
+            }
+        });
+        if let Err(e) = spawned {
+            error!("Impossible to start the asset loading thread: {:?}", e);
+        }
+        Self { receiver, loaded: Vec::new(), received: 0, total }
+    }
+
+    /// Receives the assets loaded since the last call and returns whether or not the loading is over
+    pub(crate) fn poll(&mut self) -> bool {
+        while self.received < self.total {
+            match self.receiver.try_recv() {
+                Ok(asset) => {
+                    self.received += 1;
+                    self.loaded.extend(asset);
+                }
+                Err(TryRecvError::Empty) => return false,
+                Err(TryRecvError::Disconnected) => {
+                    error!("The asset loading thread stopped before loading every asset");
+                    self.received = self.total;
+                }
+            }
+        }
+        true
+    }
+
+    pub(crate) fn received(&self) -> usize {
+        self.received
+    }
+
+    pub(crate) fn total(&self) -> usize {
+        self.total
+    }
+
+    /// Makes the loaded assets available to the engine
+    pub(crate) fn finish(self, data: &mut GameData) {
+        self.loaded.into_iter().for_each(|asset| asset.install(data));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::core::resources::font_atlas::FontAtlas;
+
+    use super::*;
+
+    #[test]
+    fn load_tileset_and_font_test() {
+        let dir = std::env::temp_dir().join(format!("scion_asset_loader_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let atlas_path = dir.join("tileset.scion");
+        std::fs::write(&atlas_path, r#"{"name":"tileset","total_tiles":1,"width":1,"height":1,"tile_width":16,"tile_height":16,"pathing":null,"tiles":{}}"#).unwrap();
+        let font_path = dir.join("font.ttf");
+        std::fs::write(&font_path, [1, 2, 3]).unwrap();
+
+        let mut data = GameData::default();
+        data.insert_resource(AssetManager::default());
+        data.insert_resource(FontAtlas::default());
+        data.assets_mut().register_tileset_atlas_and_texture("tileset", atlas_path.to_str().unwrap(), "missing.png");
+
+        let mut loader = AssetLoader::start(
+            vec![
+                AssetRequest::Tileset("tileset".to_string()),
+                AssetRequest::Font(font_path.to_str().unwrap().to_string()),
+                AssetRequest::Tilemap("unregistered".to_string()),
+            ],
+            &data,
+        );
+        assert_eq!(2, loader.total());
+        let started = Instant::now();
+        while !loader.poll() {
+            assert!(started.elapsed() < Duration::from_secs(5));
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(2, loader.received());
+        loader.finish(&mut data);
+
+        assert!(data.assets().retrieve_asset_ref_for_tileset_name("tileset").is_some());
+        assert_eq!(Some(&vec![1, 2, 3]), data.font_atlas().get_font_file(font_path.to_str().unwrap()));
+        let _r = std::fs::remove_dir_all(dir);
+    }
+}
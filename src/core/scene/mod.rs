@@ -0,0 +1,918 @@
+//! Everything that is linked to the running of scenes.
+
+use std::any::{Any, TypeId};
+use std::collections::HashSet;
+use std::sync::{Mutex, PoisonError};
+
+use downcast_rs::{Downcast, impl_downcast};
+use hecs::Entity;
+
+use crate::core::components::maths::hierarchy::Children;
+use crate::core::components::Persistent;
+use crate::core::resources::asset_manager::AssetManager;
+use crate::core::resources::events::Events;
+use crate::core::resources::time::{Time, Timers};
+use crate::core::scene::loading::{AssetLoader, AssetRequest, LoadingProgress};
+use crate::core::scene::transition::{RunningTransition, SceneTransition};
+use crate::core::world::{GameData, World};
+use crate::graphics::components::{SceneHidden, TransitionOpacity};
+
+pub mod loading;
+pub mod transition;
+
+/// Trait to implement in order to define a `Scene`.
+/// Scenes are organized in a stack : the top scene is the current one, and the scenes below it are
+/// paused, unless the scenes above them allow them to keep updating (see [`Scene::updates_scenes_below`]).
+pub trait Scene: Downcast {
+    /// Will be called once before the new game loop iteration. Useful to initialize resources and add everything you need in the world.
+    /// The payload given to the transition starting this scene can be retrieved here using [`SceneController::payload`].
+    fn on_start(&mut self, _data: &mut GameData) {}
+    /// Will be called each game loop, before the systems execution
+    fn on_update(&mut self, _data: &mut GameData) {}
+    /// Will be called at the fixed update rate (60 times per second by default), before the fixed systems execution
+    fn on_fixed_update(&mut self, _data: &mut GameData) {}
+    /// Will be called each game loop, after the systems execution
+    fn late_update(&mut self, _data: &mut GameData) {}
+    /// Will be called at the end of the frame where another scene was pushed on top of this one
+    fn on_pause(&mut self, _data: &mut GameData) {}
+    /// Will be called at the end of the frame where the scene above this one was popped
+    fn on_resume(&mut self, _data: &mut GameData) {}
+    /// Will be called for deleted scene at the end of the frame where it was deleted, and for every scene of the stack when the game exits.
+    /// Once stopped, the entities (unless [`Persistent`]), timers, event subscriptions and unused preloaded assets created while the scene was running are deleted.
+    fn on_stop(&mut self, _data: &mut GameData) {}
+    /// Assets to load on a background thread before calling `on_start`. Meanwhile, the loading scene
+    /// set with [`crate::ScionBuilder::with_loading_scene`] is the only scene updated. Default is none.
+    fn assets(&self) -> Vec<AssetRequest> {
+        Vec::new()
+    }
+    /// Whether or not the scenes below this one in the stack keep being updated. Default is false.
+    fn updates_scenes_below(&self) -> bool {
+        false
+    }
+    /// Whether or not the entities of the scenes below this one in the stack keep being rendered. Default is true.
+    fn renders_scenes_below(&self) -> bool {
+        true
+    }
+}
+
+impl_downcast!(Scene);
+
+pub(crate) enum SceneAction {
+    Update,
+    FixedUpdate,
+    Start,
+    EndFrame,
+    LateUpdate,
+    Stop,
+}
+
+/// Identifier of a scene instance in the scene stack
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub(crate) struct SceneId(usize);
+
+/// Component added to the entities created while a scene is the running one. These entities are
+/// deleted when the scene stops, unless they or one of their ancestors have the [`Persistent`] component.
+/// Entities created with a [`crate::core::components::maths::hierarchy::Parent`] belong to the scene of their parent instead.
+pub(crate) struct SceneOwned(pub(crate) SceneId);
+
+struct StackedScene {
+    id: SceneId,
+    scene: Box<dyn Scene + Send>,
+    started: bool,
+    payload: Option<Payload>,
+    /// Whether or not the assets of the scene have been requested to the loader
+    assets_requested: bool,
+}
+
+type Payload = Box<dyn Any + Send>;
+
+/// Function creating the loading scene
+pub(crate) type SceneFactory = fn() -> Box<dyn Scene + Send>;
+
+/// Assets loading of a scene, while the loading scene is displayed
+struct Loading {
+    loader: AssetLoader,
+    owner: SceneId,
+    screen: Option<StackedScene>,
+}
+
+/// `SceneMachine` is the Resource used to control the game scene.
+#[derive(Default)]
+pub(crate) struct SceneMachine {
+    stack: Vec<StackedScene>,
+    next_id: usize,
+    hidden_scenes: HashSet<SceneId>,
+    transition: Option<RunningTransition>,
+    /// Scene waiting for the middle of the transition to replace the stack
+    pending_scene: Option<(Box<dyn Scene + Send>, Option<Payload>)>,
+    /// Scenes fading out during a crossfade
+    fading_scenes: Vec<StackedScene>,
+    loading_scene: Option<SceneFactory>,
+    loading: Option<Loading>,
+}
+
+impl SceneMachine {
+    pub(crate) fn new(scene: Option<Box<dyn Scene + Send>>) -> Self {
+        let mut machine = Self::default();
+        if let Some(scene) = scene {
+            machine.push(scene, None);
+        }
+        machine
+    }
+
+    pub(crate) fn with_loading_scene(mut self, loading_scene: Option<SceneFactory>) -> Self {
+        self.loading_scene = loading_scene;
+        self
+    }
+
+    pub(crate) fn apply_scene_action(&mut self, action: SceneAction, data: &mut GameData) {
+        match action {
+            SceneAction::EndFrame if self.transition.is_some() => {
+                // Scene actions requested during a transition wait for its end
+                self.advance_transition(data);
+                self.sync_controller(data);
+                self.update_visibility(data);
+            }
+            SceneAction::EndFrame if self.loading.is_some() => {
+                // Scene actions requested during a loading wait for its end
+                self.sync_controller(data);
+                self.update_visibility(data);
+            }
+            SceneAction::EndFrame => {
+                let (action, payload) = data.scene_controller().action();
+                match action {
+                    Some(SceneTrans::SwitchWithTransition(new_scene, transition)) => {
+                        self.start_transition(new_scene, payload, transition, data);
+                    }
+                    Some(SceneTrans::Switch(new_scene)) => {
+                        while let Some(scene) = self.stack.pop() {
+                            Self::stop(scene, data);
+                        }
+                        self.push(new_scene, payload);
+                    }
+                    Some(SceneTrans::Replace(new_scene)) => {
+                        if let Some(scene) = self.stack.pop() {
+                            Self::stop(scene, data);
+                        }
+                        self.push(new_scene, payload);
+                    }
+                    Some(SceneTrans::Push(new_scene)) => {
+                        if let Some(top) = self.stack.last_mut().filter(|top| top.started) {
+                            Self::set_owner(data, Some(top.id));
+                            top.scene.on_pause(data);
+                        }
+                        self.push(new_scene, payload);
+                    }
+                    Some(SceneTrans::Pop) => {
+                        if let Some(scene) = self.stack.pop() {
+                            Self::stop(scene, data);
+                        }
+                        if let Some(top) = self.stack.last_mut().filter(|top| top.started) {
+                            Self::set_owner(data, Some(top.id));
+                            top.scene.on_resume(data);
+                        }
+                    }
+                    None => {}
+                }
+                self.sync_controller(data);
+                self.update_visibility(data);
+            }
+            SceneAction::Stop => {
+                while let Some(scene) = self.stack.pop() {
+                    Self::stop(scene, data);
+                }
+                while let Some(scene) = self.fading_scenes.pop() {
+                    Self::stop(scene, data);
+                }
+                self.pending_scene = None;
+                if let Some(transition) = self.transition.take() {
+                    transition.end(data);
+                }
+                if let Some(screen) = self.loading.take().and_then(|loading| loading.screen) {
+                    Self::stop(screen, data);
+                }
+                self.sync_controller(data);
+            }
+            _ if self.run_loading(&action, data) => {}
+            _ => {
+                for index in self.first_updated_index()..self.stack.len() {
+                    Self::set_owner(data, Some(self.stack[index].id));
+                    let starting = matches!(action, SceneAction::Start | SceneAction::Update) && !self.stack[index].started;
+                    if starting && !self.stack[index].assets_requested {
+                        self.stack[index].assets_requested = true;
+                        let requests = self.stack[index].scene.assets();
+                        if !requests.is_empty() {
+                            self.start_loading(self.stack[index].id, requests, data);
+                            self.run_loading_screen(&action, data);
+                            break;
+                        }
+                    }
+                    if starting {
+                        self.stack[index].started = true;
+                        self.sync_controller(data);
+                        Self::start(&mut self.stack[index], data);
+                    }
+                    Self::run(&mut self.stack[index], &action, data);
+                }
+            }
+        }
+        Self::set_owner(data, self.stack.last().map(|top| top.id));
+    }
+
+    /// Calls `on_start` on `scene`, giving it its payload
+    fn start(scene: &mut StackedScene, data: &mut GameData) {
+        data.scene_controller().requests().payload = scene.payload.take();
+        scene.scene.on_start(data);
+        data.scene_controller().requests().payload = None;
+    }
+
+    fn run(scene: &mut StackedScene, action: &SceneAction, data: &mut GameData) {
+        match action {
+            SceneAction::Update => scene.scene.on_update(data),
+            SceneAction::FixedUpdate if scene.started => scene.scene.on_fixed_update(data),
+            SceneAction::LateUpdate if scene.started => scene.scene.late_update(data),
+            _ => {}
+        }
+    }
+
+    fn start_loading(&mut self, owner: SceneId, requests: Vec<AssetRequest>, data: &mut GameData) {
+        let loader = AssetLoader::start(requests, data);
+        let screen = self.loading_scene.map(|factory| {
+            let screen = StackedScene { id: SceneId(self.next_id), scene: factory(), started: false, payload: None, assets_requested: true };
+            self.next_id += 1;
+            screen
+        });
+        if let Some(mut progress) = data.get_resource_mut::<LoadingProgress>() {
+            progress.update(true, 0, loader.total());
+        }
+        self.loading = Some(Loading { loader, owner, screen });
+    }
+
+    /// Runs `action` on the loading scene while assets are loading. Once every asset is loaded,
+    /// stops the loading scene and returns false to let the scenes of the stack run.
+    fn run_loading(&mut self, action: &SceneAction, data: &mut GameData) -> bool {
+        let Some(loading) = self.loading.as_mut() else { return false; };
+        if matches!(action, SceneAction::Update) && loading.loader.poll() {
+            let Loading { loader, owner, screen } = self.loading.take().expect("The loading has been checked above");
+            if let Some(screen) = screen {
+                Self::stop(screen, data);
+            }
+            if let Some(mut progress) = data.get_resource_mut::<LoadingProgress>() {
+                progress.update(false, loader.total(), loader.total());
+            }
+            Self::set_owner(data, Some(owner));
+            loader.finish(data);
+            Self::set_owner(data, self.stack.last().map(|top| top.id));
+            return false;
+        }
+        if let Some(mut progress) = data.get_resource_mut::<LoadingProgress>() {
+            progress.update(true, loading.loader.received(), loading.loader.total());
+        }
+        self.run_loading_screen(action, data);
+        true
+    }
+
+    fn run_loading_screen(&mut self, action: &SceneAction, data: &mut GameData) {
+        if let Some(screen) = self.loading_screen() {
+            let starting = matches!(action, SceneAction::Start | SceneAction::Update) && !screen.started;
+            screen.started |= starting;
+            Self::set_owner(data, Some(screen.id));
+            if starting {
+                self.sync_controller(data);
+                self.loading_screen().into_iter().for_each(|screen| Self::start(screen, data));
+            }
+            self.loading_screen().into_iter().for_each(|screen| Self::run(screen, action, data));
+        }
+        Self::set_owner(data, self.stack.last().map(|top| top.id));
+    }
+
+    fn loading_screen(&mut self) -> Option<&mut StackedScene> {
+        self.loading.as_mut().and_then(|loading| loading.screen.as_mut())
+    }
+
+    fn start_transition(&mut self, scene: Box<dyn Scene + Send>, payload: Option<Payload>, transition: SceneTransition, data: &mut GameData) {
+        let transition = RunningTransition::start(transition, data);
+        if transition.is_crossfade() {
+            self.fading_scenes = std::mem::take(&mut self.stack);
+            self.push(scene, payload);
+        } else {
+            self.pending_scene = Some((scene, payload));
+        }
+        self.transition = Some(transition);
+        self.update_transition_opacity(data);
+    }
+
+    fn advance_transition(&mut self, data: &mut GameData) {
+        let Some(transition) = self.transition.as_mut() else { return; };
+        let delta = data.get_resource::<Time>().map(|time| time.unscaled_delta_duration()).unwrap_or_default();
+        transition.advance(delta, data);
+        if transition.should_switch() {
+            if let Some((scene, payload)) = self.pending_scene.take() {
+                while let Some(scene) = self.stack.pop() {
+                    Self::stop(scene, data);
+                }
+                self.push(scene, payload);
+            }
+        }
+        self.update_transition_opacity(data);
+
+        if self.transition.as_ref().is_some_and(|t| t.is_finished()) {
+            while let Some(scene) = self.fading_scenes.pop() {
+                Self::stop(scene, data);
+            }
+            let faded: Vec<Entity> = data.query::<&TransitionOpacity>().with::<&SceneOwned>().iter().map(|(e, _)| e).collect();
+            faded.into_iter().for_each(|e| {
+                let _r = data.remove_component::<TransitionOpacity>(e);
+            });
+            if let Some(transition) = self.transition.take() {
+                transition.end(data);
+            }
+        }
+    }
+
+    /// During a crossfade, fades out the entities of the old scenes and fades in the ones of the new scenes
+    fn update_transition_opacity(&self, data: &mut GameData) {
+        let Some(progress) = self.transition.as_ref().filter(|t| t.is_crossfade()).map(|t| t.progress()) else { return; };
+        let fading: HashSet<SceneId> = self.fading_scenes.iter().map(|s| s.id).collect();
+        let opacities: Vec<(Entity, f32)> = data
+            .query::<&SceneOwned>()
+            .iter()
+            .map(|(e, owner)| (e, if fading.contains(&owner.0) { 1. - progress } else { progress }))
+            .collect();
+        opacities.into_iter().for_each(|(e, opacity)| {
+            let _r = data.add_components(e, (TransitionOpacity(opacity),));
+        });
+    }
+
+    fn push(&mut self, scene: Box<dyn Scene + Send>, payload: Option<Payload>) {
+        self.stack.push(StackedScene { id: SceneId(self.next_id), scene, started: false, payload, assets_requested: false });
+        self.next_id += 1;
+    }
+
+    /// Stops `scene`, then deletes the entities, timers and subscriptions it owns
+    fn stop(mut scene: StackedScene, data: &mut GameData) {
+        if scene.started {
+            Self::set_owner(data, Some(scene.id));
+            scene.scene.on_stop(data);
+        }
+        let mut persistent: Vec<Entity> = data.query::<&Persistent>().iter().map(|(e, _)| e).collect();
+        let mut kept = HashSet::new();
+        while let Some(entity) = persistent.pop() {
+            if kept.insert(entity) {
+                if let Ok(Some(children)) = data.entry::<&Children>(entity).as_mut().map(|c| c.get()) {
+                    persistent.extend(children.0.iter());
+                }
+            }
+        }
+        let owned: Vec<Entity> = data
+            .query::<&SceneOwned>()
+            .iter()
+            .filter(|(e, owner)| owner.0 == scene.id && !kept.contains(e))
+            .map(|(e, _)| e)
+            .collect();
+        owned.into_iter().for_each(|e| {
+            let _r = data.remove(e);
+        });
+        if let Some(mut timers) = data.get_resource_mut::<Timers>() {
+            timers.delete_scene_timers(scene.id);
+        }
+        if let Some(mut events) = data.get_resource_mut::<Events>() {
+            events.unsubscribe_scene(scene.id);
+        }
+        if let Some(mut assets) = data.get_resource_mut::<AssetManager>() {
+            assets.delete_scene_preloads(scene.id);
+        }
+    }
+
+    /// Makes `owner` the owner of the entities, timers and subscriptions created from now
+    fn set_owner(data: &mut GameData, owner: Option<SceneId>) {
+        data.subworld.scene_owner = owner;
+        if let Some(mut timers) = data.get_resource_mut::<Timers>() {
+            timers.scene_owner = owner;
+        }
+        if let Some(mut events) = data.get_resource_mut::<Events>() {
+            events.scene_owner = owner;
+        }
+        if let Some(mut assets) = data.get_resource_mut::<AssetManager>() {
+            assets.scene_owner = owner;
+        }
+    }
+
+    /// Index of the lowest scene updated, i.e. not paused by the scenes above it
+    fn first_updated_index(&self) -> usize {
+        self.stack
+            .iter()
+            .rposition(|s| !s.scene.updates_scenes_below())
+            .unwrap_or(0)
+    }
+
+    fn sync_controller(&self, data: &mut GameData) {
+        let scene_type = |s: &StackedScene| (*s.scene).as_any().type_id();
+        if let Some(screen) = self.loading.as_ref().and_then(|loading| loading.screen.as_ref()) {
+            let current = Some(screen).filter(|screen| screen.started).map(scene_type);
+            data.scene_controller().set_scene_types(current, current.into_iter().collect());
+            return;
+        }
+        let current = self.stack.last().filter(|top| top.started).map(scene_type);
+        let active = self.stack[self.first_updated_index()..].iter().filter(|s| s.started).map(scene_type).collect();
+        data.scene_controller().set_scene_types(current, active);
+    }
+
+    /// Hides the entities of the scenes covered by a scene that doesn't render the scenes below it
+    fn update_visibility(&mut self, data: &mut GameData) {
+        let mut hidden_scenes = HashSet::new();
+        let mut rendered = self
+            .loading
+            .as_ref()
+            .and_then(|loading| loading.screen.as_ref())
+            .is_none_or(|screen| screen.scene.renders_scenes_below());
+        for scene in self.stack.iter().rev() {
+            if !rendered {
+                hidden_scenes.insert(scene.id);
+            }
+            rendered &= scene.scene.renders_scenes_below();
+        }
+        if hidden_scenes.is_empty() && self.hidden_scenes.is_empty() {
+            return;
+        }
+
+        let to_show: Vec<_> = data
+            .query::<&SceneOwned>()
+            .with::<&SceneHidden>()
+            .iter()
+            .filter(|(_, owner)| !hidden_scenes.contains(&owner.0))
+            .map(|(e, _)| e)
+            .collect();
+        to_show.into_iter().for_each(|e| {
+            let _r = data.remove_component::<SceneHidden>(e);
+        });
+        let to_hide: Vec<_> = data
+            .query::<&SceneOwned>()
+            .without::<&SceneHidden>()
+            .iter()
+            .filter(|(_, owner)| hidden_scenes.contains(&owner.0))
+            .map(|(e, _)| e)
+            .collect();
+        to_hide.into_iter().for_each(|e| {
+            let _r = data.add_components(e, (SceneHidden,));
+        });
+        self.hidden_scenes = hidden_scenes;
+    }
+}
+
+pub(crate) enum SceneTrans {
+    Switch(Box<dyn Scene + Send>),
+    SwitchWithTransition(Box<dyn Scene + Send>, SceneTransition),
+    Replace(Box<dyn Scene + Send>),
+    Push(Box<dyn Scene + Send>),
+    Pop,
+}
+
+/// Scenes and payloads handed over to the `SceneMachine`
+#[derive(Default)]
+struct SceneRequests {
+    /// scene action that has to be executed at the end of the frame
+    action: Option<SceneTrans>,
+    /// payload given to the scene started by `action`
+    next_payload: Option<Payload>,
+    /// payload of the scene being started
+    payload: Option<Payload>,
+}
+
+/// `SceneController` is the Resource used to control the game scenes.
+#[derive(Default)]
+pub struct SceneController {
+    /// Scenes and payloads are only `Send`, the mutex makes the controller shareable with the parallel systems.
+    /// It is only accessed through `&mut self`, so it is never locked
+    requests: Mutex<SceneRequests>,
+    /// type of the scene on top of the stack
+    current_scene_type: Option<TypeId>,
+    /// types of the scenes updated during the frame
+    active_scene_types: Vec<TypeId>,
+}
+
+impl SceneController {
+    /// Replace every scene of the stack with the scene created from type `T`. (Useful for level switching).
+    /// Note that the scenes' stop will happen at the end of the frame.
+    pub fn switch<T: Scene + Default + Send + 'static>(&mut self) -> &mut Self {
+        self.switch_to(Box::<T>::default())
+    }
+
+    /// Replace every scene of the stack with the already constructed `scene`.
+    /// Note that the scenes' stop will happen at the end of the frame.
+    pub fn switch_to(&mut self, scene: Box<dyn Scene + Send>) -> &mut Self {
+        self.set_action(SceneTrans::Switch(scene))
+    }
+
+    /// Replace every scene of the stack with the scene created from type `T`, playing `transition`.
+    /// Scene actions requested while the transition is playing are applied once it ends.
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use scion::core::scene::Scene;
+    /// use scion::core::scene::transition::SceneTransition;
+    /// use scion::core::world::GameData;
+    /// use scion::graphics::components::color::Color;
+    ///
+    /// #[derive(Default)]
+    /// struct Level;
+    /// impl Scene for Level {}
+    ///
+    /// fn start_level(data: &mut GameData) {
+    ///     data.scene_controller().switch_with_transition::<Level>(SceneTransition::Fade {
+    ///         color: Color::new_rgb(0, 0, 0),
+    ///         duration: Duration::from_millis(800),
+    ///     });
+    /// }
+    /// ```
+    pub fn switch_with_transition<T: Scene + Default + Send + 'static>(&mut self, transition: SceneTransition) -> &mut Self {
+        self.switch_to_with_transition(Box::<T>::default(), transition)
+    }
+
+    /// Replace every scene of the stack with the already constructed `scene`, playing `transition`.
+    pub fn switch_to_with_transition(&mut self, scene: Box<dyn Scene + Send>, transition: SceneTransition) -> &mut Self {
+        self.set_action(SceneTrans::SwitchWithTransition(scene, transition))
+    }
+
+    /// Replace the scene on top of the stack with the scene created from type `T`.
+    /// Note that the scene's stop will happen at the end of the frame.
+    pub fn replace<T: Scene + Default + Send + 'static>(&mut self) -> &mut Self {
+        self.set_action(SceneTrans::Replace(Box::<T>::default()))
+    }
+
+    /// Push the scene created from type `T` on top of the stack, pausing the current one (Useful for menus and overlays).
+    /// Note that the current scene's pause will happen at the end of the frame.
+    pub fn push<T: Scene + Default + Send + 'static>(&mut self) -> &mut Self {
+        self.set_action(SceneTrans::Push(Box::<T>::default()))
+    }
+
+    /// Remove the scene on top of the stack, resuming the one below it.
+    /// Note that the scene's stop will happen at the end of the frame.
+    pub fn pop(&mut self) {
+        self.set_action(SceneTrans::Pop);
+    }
+
+    /// Attaches `payload` to the scene started by the last requested transition. The scene can
+    /// retrieve it in its `on_start` using [`SceneController::payload`].
+    /// ```no_run
+    /// use scion::core::scene::Scene;
+    /// use scion::core::world::GameData;
+    ///
+    /// struct Level;
+    /// impl Scene for Level {
+    ///     fn on_start(&mut self, data: &mut GameData) {
+    ///         let _level_number = data.scene_controller().payload::<usize>().unwrap_or(1);
+    ///     }
+    /// }
+    ///
+    /// fn next_level(data: &mut GameData, level_number: usize) {
+    ///     data.scene_controller().switch_to(Box::new(Level)).with_payload(level_number);
+    /// }
+    /// ```
+    pub fn with_payload<P: Send + 'static>(&mut self, payload: P) {
+        self.requests().next_payload = Some(Box::new(payload));
+    }
+
+    /// Takes the payload given to the transition starting the current scene, if it is of type `P`.
+    /// Only available during the scene's `on_start`.
+    pub fn payload<P: 'static>(&mut self) -> Option<P> {
+        let requests = self.requests();
+        match requests.payload.take()?.downcast::<P>() {
+            Ok(payload) => Some(*payload),
+            Err(payload) => {
+                requests.payload = Some(payload);
+                None
+            }
+        }
+    }
+
+    /// Returns whether or not the scene on top of the stack is of type `T`
+    pub fn is_current<T: Scene>(&self) -> bool {
+        self.current_scene_type == Some(TypeId::of::<T>())
+    }
+
+    /// Returns whether or not a scene of type `T` is updated, being on top of the stack or below scenes letting it update
+    pub fn is_active<T: Scene>(&self) -> bool {
+        self.active_scene_types.contains(&TypeId::of::<T>())
+    }
+
+    pub(crate) fn set_scene_types(&mut self, current: Option<TypeId>, active: Vec<TypeId>) {
+        self.current_scene_type = current;
+        self.active_scene_types = active;
+    }
+
+    fn set_action(&mut self, action: SceneTrans) -> &mut Self {
+        let requests = self.requests();
+        requests.action = Some(action);
+        requests.next_payload = None;
+        self
+    }
+
+    pub(crate) fn action(&mut self) -> (Option<SceneTrans>, Option<Payload>) {
+        let requests = self.requests();
+        (requests.action.take(), requests.next_payload.take())
+    }
+
+    fn requests(&mut self) -> &mut SceneRequests {
+        self.requests.get_mut().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::components::maths::hierarchy::Parent;
+    use crate::core::resources::time::TimerType;
+    use crate::graphics::components::color::Color;
+    use crate::core::state::GameState;
+
+    use super::*;
+
+    #[derive(Default)]
+    struct A;
+
+    #[derive(Default)]
+    struct B;
+
+    /// Overlay letting the scenes below update, without rendering them
+    #[derive(Default)]
+    struct Overlay;
+
+    fn log(data: &mut GameData, entry: &str) {
+        let log = data.game_state().get_text("log").unwrap_or_default();
+        data.game_state_mut().set_text("log", &format!("{}{};", log, entry));
+    }
+
+    impl Scene for A {
+        fn on_start(&mut self, data: &mut GameData) {
+            data.push((1_u8,));
+            log(data, "A start");
+        }
+        fn on_update(&mut self, data: &mut GameData) {
+            log(data, "A update");
+        }
+        fn on_pause(&mut self, data: &mut GameData) {
+            log(data, "A pause");
+        }
+        fn on_resume(&mut self, data: &mut GameData) {
+            log(data, "A resume");
+        }
+        fn on_stop(&mut self, data: &mut GameData) {
+            log(data, "A stop");
+        }
+    }
+
+    impl Scene for B {
+        fn on_update(&mut self, data: &mut GameData) {
+            log(data, "B update");
+        }
+        fn on_stop(&mut self, data: &mut GameData) {
+            log(data, "B stop");
+        }
+    }
+
+    impl Scene for Overlay {
+        fn on_update(&mut self, data: &mut GameData) {
+            log(data, "Overlay update");
+        }
+        fn updates_scenes_below(&self) -> bool {
+            true
+        }
+        fn renders_scenes_below(&self) -> bool {
+            false
+        }
+    }
+
+    #[derive(Default)]
+    struct LoadingScreen;
+
+    impl Scene for LoadingScreen {
+        fn on_start(&mut self, data: &mut GameData) {
+            data.push((3_u16,));
+        }
+        fn on_update(&mut self, data: &mut GameData) {
+            log(data, "Loading update");
+        }
+    }
+
+    /// Scene needing the font located at the given path
+    struct Heavy(String);
+
+    impl Scene for Heavy {
+        fn on_start(&mut self, data: &mut GameData) {
+            assert!(data.font_atlas().get_font_file(&self.0).is_some());
+            log(data, "Heavy start");
+        }
+        fn assets(&self) -> Vec<AssetRequest> {
+            vec![AssetRequest::Font(self.0.clone())]
+        }
+    }
+
+    fn game_data() -> GameData {
+        let mut data = GameData::default();
+        data.insert_resource(SceneController::default());
+        data.insert_resource(GameState::default());
+        data
+    }
+
+    fn frame(machine: &mut SceneMachine, data: &mut GameData) -> String {
+        data.game_state_mut().set_text("log", "");
+        machine.apply_scene_action(SceneAction::Update, data);
+        machine.apply_scene_action(SceneAction::EndFrame, data);
+        data.game_state().get_text("log").unwrap_or_default()
+    }
+
+    #[test]
+    fn switch_scene_should_replace_at_same_index() {
+        let mut data = game_data();
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        data.scene_controller().switch::<B>();
+        assert_eq!("A update;A stop;", frame(&mut machine, &mut data));
+        assert_eq!("B update;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<B>());
+    }
+
+    struct Level(usize);
+
+    impl Scene for Level {
+        fn on_start(&mut self, data: &mut GameData) {
+            assert!(data.scene_controller().payload::<String>().is_none());
+            let payload = data.scene_controller().payload::<usize>().unwrap();
+            log(data, &format!("Level {} start with {}", self.0, payload));
+        }
+    }
+
+    #[test]
+    fn switch_to_with_payload_test() {
+        let mut data = game_data();
+        let mut machine = SceneMachine::new(Some(Box::new(B)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        data.scene_controller().switch_to(Box::new(Level(3))).with_payload(42_usize);
+        frame(&mut machine, &mut data);
+        assert_eq!("Level 3 start with 42;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<Level>());
+        assert!(data.scene_controller().payload::<usize>().is_none());
+    }
+
+    fn timed_frame(machine: &mut SceneMachine, data: &mut GameData, millis: u64) -> String {
+        data.get_resource_mut::<Time>().unwrap().advance(std::time::Duration::from_millis(millis));
+        frame(machine, data)
+    }
+
+    #[test]
+    fn fade_transition_switches_in_the_middle_test() {
+        let mut data = game_data();
+        data.insert_resource(Time::default());
+        data.insert_resource(crate::core::resources::window::Window::new((10, 10), 1.));
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        let fade = SceneTransition::Fade { color: Color::new_rgb(0, 0, 0), duration: std::time::Duration::from_secs(1) };
+        data.scene_controller().switch_with_transition::<B>(fade);
+        assert_eq!("A update;", timed_frame(&mut machine, &mut data, 0));
+        data.scene_controller().push::<Overlay>();
+        assert_eq!("A update;", timed_frame(&mut machine, &mut data, 300));
+        assert_eq!("A update;A stop;", timed_frame(&mut machine, &mut data, 300));
+        assert_eq!("B update;", timed_frame(&mut machine, &mut data, 300));
+        assert_eq!(1, data.query::<&TransitionOpacity>().iter().count());
+        assert_eq!("B update;", timed_frame(&mut machine, &mut data, 300));
+        assert_eq!(0, data.query::<&TransitionOpacity>().iter().count());
+        assert!(data.scene_controller().is_current::<B>());
+        // The push requested during the transition is applied once it has ended
+        assert_eq!("B update;", timed_frame(&mut machine, &mut data, 300));
+        assert_eq!("B update;Overlay update;", timed_frame(&mut machine, &mut data, 300));
+    }
+
+    #[test]
+    fn crossfade_transition_test() {
+        let mut data = game_data();
+        data.insert_resource(Time::default());
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+        let old_entity = data.query::<&u8>().iter().next().unwrap().0;
+
+        data.scene_controller().switch_with_transition::<B>(SceneTransition::Crossfade { duration: std::time::Duration::from_secs(1) });
+        assert_eq!("A update;", timed_frame(&mut machine, &mut data, 0));
+        assert_eq!(1., data.entry::<&TransitionOpacity>(old_entity).unwrap().get().unwrap().0);
+        assert_eq!("B update;", timed_frame(&mut machine, &mut data, 500));
+        assert_eq!(0.5, data.entry::<&TransitionOpacity>(old_entity).unwrap().get().unwrap().0);
+        assert_eq!("B update;A stop;", timed_frame(&mut machine, &mut data, 500));
+        assert!(!data.contains(old_entity));
+    }
+
+    #[test]
+    fn scene_owned_data_is_deleted_on_stop_test() {
+        let mut data = game_data();
+        data.insert_resource(Timers::default());
+        data.insert_resource(Events::default());
+        data.insert_resource(AssetManager::default());
+        let _r = data.events().create_topic("topic", Default::default());
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        let owned = data.query::<&u8>().iter().next().unwrap().0;
+        let child = data.push((2_u8,));
+        let persistent = data.push((Persistent, Children(vec![child])));
+        let _r = data.timers().add_timer("owned", TimerType::Manual, 1.);
+        let _r = data.timers().add_timer("persistent", TimerType::Manual, 1.).map(|t| t.set_persistent());
+        let subscription = data.events().subscribe("topic", Default::default()).unwrap();
+        let persistent_subscription = data.events().subscribe("topic", Default::default()).unwrap();
+        let _r = data.events().set_persistent(&persistent_subscription);
+        data.assets_mut().add_preloaded_image("unused.png".to_string(), image::DynamicImage::new_rgba8(1, 1));
+
+        data.scene_controller().switch::<B>();
+        frame(&mut machine, &mut data);
+        assert!(!data.contains(owned));
+        assert!(data.contains(persistent));
+        assert!(data.contains(child));
+        assert!(!data.timers().exists("owned"));
+        assert!(data.timers().exists("persistent"));
+        assert!(data.events().mark_read(&subscription).is_err());
+        assert!(data.events().mark_read(&persistent_subscription).is_ok());
+        assert!(data.assets_mut().take_preloaded_image("unused.png").is_none());
+    }
+
+    #[test]
+    fn push_and_pop_scenes_test() {
+        let mut data = game_data();
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        data.scene_controller().push::<B>();
+        assert_eq!("A update;A pause;", frame(&mut machine, &mut data));
+        assert_eq!("B update;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<B>());
+        assert!(!data.scene_controller().is_active::<A>());
+
+        data.scene_controller().pop();
+        assert_eq!("B update;B stop;A resume;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<A>());
+
+        data.scene_controller().push::<B>();
+        frame(&mut machine, &mut data);
+        data.scene_controller().replace::<Overlay>();
+        assert_eq!("B update;B stop;", frame(&mut machine, &mut data));
+        assert_eq!("A update;Overlay update;", frame(&mut machine, &mut data));
+
+        data.game_state_mut().set_text("log", "");
+        machine.apply_scene_action(SceneAction::Stop, &mut data);
+        assert_eq!("A stop;", data.game_state().get_text("log").unwrap());
+        assert!(!data.scene_controller().is_active::<A>());
+    }
+
+    #[test]
+    fn overlay_updates_and_hides_lower_scenes_test() {
+        let mut data = game_data();
+        let mut machine = SceneMachine::new(Some(Box::new(A)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+        let level_entity = data.query::<&u8>().iter().next().unwrap().0;
+
+        data.scene_controller().push::<Overlay>();
+        frame(&mut machine, &mut data);
+        assert!(data.entry::<&SceneHidden>(level_entity).unwrap().get().is_some());
+        assert_eq!("A update;Overlay update;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<Overlay>());
+        assert!(data.scene_controller().is_active::<A>());
+
+        let overlay_entity = data.push((2_u8,));
+        // Created by a system while the overlay runs, but for an entity of the level
+        let level_child = data.push((3_u8, Parent(level_entity)));
+        data.scene_controller().pop();
+        frame(&mut machine, &mut data);
+        assert!(data.entry::<&SceneHidden>(level_entity).unwrap().get().is_none());
+        assert!(!data.contains(overlay_entity));
+        assert!(data.contains(level_child));
+    }
+
+    #[test]
+    fn scene_assets_are_loaded_before_start_test() {
+        let font_path = std::env::temp_dir().join(format!("scion_scene_font_{}.ttf", std::process::id()));
+        std::fs::write(&font_path, [0, 1]).unwrap();
+        let font_path = font_path.to_str().unwrap().to_string();
+        let mut data = game_data();
+        data.insert_resource(crate::core::resources::asset_manager::AssetManager::default());
+        data.insert_resource(crate::core::resources::font_atlas::FontAtlas::default());
+        data.insert_resource(LoadingProgress::default());
+        let mut machine = SceneMachine::new(Some(Box::new(A))).with_loading_scene(Some(|| Box::new(LoadingScreen)));
+        machine.apply_scene_action(SceneAction::Start, &mut data);
+
+        data.scene_controller().switch_to(Box::new(Heavy(font_path.to_string())));
+        assert_eq!("A update;A stop;", frame(&mut machine, &mut data));
+        assert_eq!("Loading update;", frame(&mut machine, &mut data));
+        assert!(data.scene_controller().is_current::<LoadingScreen>());
+        assert!(data.get_resource::<LoadingProgress>().unwrap().is_loading());
+        assert_eq!(1, data.query::<&u16>().iter().count());
+
+        let started = std::time::Instant::now();
+        while !frame(&mut machine, &mut data).contains("Heavy start") {
+            assert!(started.elapsed() < std::time::Duration::from_secs(5));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        assert!(data.scene_controller().is_current::<Heavy>());
+        assert!(!data.get_resource::<LoadingProgress>().unwrap().is_loading());
+        assert_eq!(1., data.get_resource::<LoadingProgress>().unwrap().progress());
+        assert_eq!(0, data.query::<&u16>().iter().count());
+        let _r = std::fs::remove_file(font_path);
+    }
+}
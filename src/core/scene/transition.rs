@@ -0,0 +1,190 @@
+use std::time::Duration;
+
+use hecs::Entity;
+
+use crate::core::components::maths::transform::Transform;
+use crate::core::components::Persistent;
+use crate::core::world::{GameData, World};
+use crate::graphics::components::color::Color;
+use crate::graphics::components::material::Material;
+use crate::graphics::components::TransitionOpacity;
+use crate::graphics::components::ui::ui_image::UiImage;
+use crate::graphics::components::ui::UiComponent;
+
+/// Layer of the overlay drawn over the scenes during a transition
+const OVERLAY_LAYER: usize = 1_000_000;
+
+/// Animation played when switching scenes with [`crate::core::scene::SceneController::switch_with_transition`]
+#[derive(Clone, Debug)]
+pub enum SceneTransition {
+    /// Fades the screen to `color`, switches the scenes, then fades back from `color`.
+    /// The old scene is stopped and the new one started in the middle of the transition.
+    Fade { color: Color, duration: Duration },
+    /// Fades the old scene out while the new one fades in.
+    /// The new scene is started at the beginning of the transition and the old one stopped at its end.
+    Crossfade { duration: Duration },
+    /// A `color` curtain crosses the screen toward `direction`, switching the scenes once it covers the whole screen.
+    /// The old scene is stopped and the new one started in the middle of the transition.
+    Wipe { color: Color, direction: WipeDirection, duration: Duration },
+}
+
+/// Direction toward which the curtain of a [`SceneTransition::Wipe`] moves
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WipeDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+impl SceneTransition {
+    fn duration(&self) -> Duration {
+        match self {
+            SceneTransition::Fade { duration, .. }
+            | SceneTransition::Crossfade { duration }
+            | SceneTransition::Wipe { duration, .. } => *duration,
+        }
+    }
+}
+
+/// Marker of the overlay entity drawn during a transition
+pub(crate) struct TransitionOverlay;
+
+/// State of the transition being played by the `SceneMachine`
+pub(crate) struct RunningTransition {
+    transition: SceneTransition,
+    elapsed: Duration,
+    overlay: Option<Entity>,
+}
+
+impl RunningTransition {
+    pub(crate) fn start(transition: SceneTransition, data: &mut GameData) -> Self {
+        let overlay = match &transition {
+            SceneTransition::Fade { color, .. } | SceneTransition::Wipe { color, .. } => {
+                let (width, height) = { let window = data.window(); (window.width(), window.height()) };
+                Some(data.push((
+                    UiImage::new(width as f32, height as f32),
+                    UiComponent,
+                    Material::Diffuse(color.clone()),
+                    Transform::from_xyz(0., 0., OVERLAY_LAYER),
+                    TransitionOpacity(0.),
+                    Persistent,
+                    TransitionOverlay,
+                )))
+            }
+            SceneTransition::Crossfade { .. } => None,
+        };
+        let mut running = Self { transition, elapsed: Duration::ZERO, overlay };
+        running.update_overlay(data);
+        running
+    }
+
+    pub(crate) fn is_crossfade(&self) -> bool {
+        matches!(self.transition, SceneTransition::Crossfade { .. })
+    }
+
+    /// Progress of the transition, between 0 and 1
+    pub(crate) fn progress(&self) -> f32 {
+        let duration = self.transition.duration();
+        if duration.is_zero() {
+            return 1.;
+        }
+        (self.elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.)
+    }
+
+    /// Whether or not the old scene has to be replaced by the new one
+    pub(crate) fn should_switch(&self) -> bool {
+        self.is_crossfade() || self.progress() >= 0.5
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.elapsed >= self.transition.duration()
+    }
+
+    pub(crate) fn advance(&mut self, delta: Duration, data: &mut GameData) {
+        self.elapsed += delta;
+        self.update_overlay(data);
+    }
+
+    /// Removes the overlay of the transition
+    pub(crate) fn end(self, data: &mut GameData) {
+        if let Some(overlay) = self.overlay {
+            let _r = data.remove(overlay);
+        }
+    }
+
+    fn update_overlay(&mut self, data: &mut GameData) {
+        let Some(overlay) = self.overlay else { return; };
+        // -1 when the transition starts, 0 when the overlay covers the screen, 1 when it ends
+        let position = self.progress() * 2. - 1.;
+        let (width, height) = { let window = data.window(); (window.width() as f32, window.height() as f32) };
+        match self.transition {
+            SceneTransition::Fade { .. } => {
+                let _r = data.add_components(overlay, (TransitionOpacity(1. - position.abs()),));
+            }
+            SceneTransition::Wipe { direction, .. } => {
+                let _r = data.add_components(overlay, (TransitionOpacity(1.),));
+                if let Ok(transform) = data.entry_mut::<&mut Transform>(overlay) {
+                    match direction {
+                        WipeDirection::Left => transform.set_x(-position * width),
+                        WipeDirection::Right => transform.set_x(position * width),
+                        WipeDirection::Up => transform.set_y(-position * height),
+                        WipeDirection::Down => transform.set_y(position * height),
+                    }
+                }
+            }
+            SceneTransition::Crossfade { .. } => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::resources::window::Window;
+
+    use super::*;
+
+    fn opacity(data: &GameData, entity: Entity) -> f32 {
+        data.entry::<&TransitionOpacity>(entity).unwrap().get().unwrap().0
+    }
+
+    #[test]
+    fn fade_overlay_test() {
+        let mut data = GameData::default();
+        data.insert_resource(Window::new((100, 50), 1.));
+        let mut fade = RunningTransition::start(
+            SceneTransition::Fade { color: Color::new_rgb(0, 0, 0), duration: Duration::from_secs(2) },
+            &mut data,
+        );
+        let overlay = fade.overlay.unwrap();
+        assert_eq!(0., opacity(&data, overlay));
+        fade.advance(Duration::from_millis(500), &mut data);
+        assert_eq!(0.5, opacity(&data, overlay));
+        assert!(!fade.should_switch());
+        fade.advance(Duration::from_millis(500), &mut data);
+        assert_eq!(1., opacity(&data, overlay));
+        assert!(fade.should_switch());
+        fade.advance(Duration::from_millis(1500), &mut data);
+        assert_eq!(0., opacity(&data, overlay));
+        assert!(fade.is_finished());
+        fade.end(&mut data);
+        assert!(!data.contains(overlay));
+    }
+
+    #[test]
+    fn wipe_overlay_test() {
+        let mut data = GameData::default();
+        data.insert_resource(Window::new((100, 50), 1.));
+        let mut wipe = RunningTransition::start(
+            SceneTransition::Wipe { color: Color::new_rgb(0, 0, 0), direction: WipeDirection::Left, duration: Duration::from_secs(1) },
+            &mut data,
+        );
+        let overlay = wipe.overlay.unwrap();
+        let x = |data: &GameData| data.entry::<&Transform>(overlay).unwrap().get().unwrap().translation().x();
+        assert_eq!(100., x(&data));
+        wipe.advance(Duration::from_millis(500), &mut data);
+        assert_eq!(0., x(&data));
+        wipe.advance(Duration::from_millis(500), &mut data);
+        assert_eq!(-100., x(&data));
+    }
+}
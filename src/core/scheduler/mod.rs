@@ -0,0 +1,457 @@
+//! Everything that is linked to the registration and the ordering of systems.
+
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
+
+use crate::core::resources::commands::apply_commands;
+use crate::core::state::GameState;
+use crate::core::world::GameData;
+
+pub use parallel::{ExecutionMode, ParallelSystem, SystemAccess};
+pub use run_criteria::RunCriteria;
+
+mod ordering;
+mod parallel;
+mod run_criteria;
+
+/// Trait to implement in order to define a `System` that can own a state.
+/// Any `FnMut(&mut GameData)` function or closure is already a `System`.
+pub trait System: Send + 'static {
+    /// Will be called each game loop, by the scheduler.
+    fn run(&mut self, data: &mut GameData);
+    /// Will be called once, before the first scene start. Useful to create subscriptions or resources.
+    fn on_init(&mut self, _data: &mut GameData) {}
+    /// Will be called once, when the game loop stops.
+    fn on_shutdown(&mut self, _data: &mut GameData) {}
+}
+
+impl<F> System for F
+where
+    F: FnMut(&mut GameData) + Send + 'static,
+{
+    fn run(&mut self, data: &mut GameData) {
+        self(data)
+    }
+}
+
+/// `Stage` is a step of the game loop in which systems are executed. Stages are executed in their declaration order.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Stage {
+    /// Executed at a fixed rate, after the scene's fixed update. Several fixed steps can be executed
+    /// during a frame to catch up with the real time. Constraints can't reference systems of other stages.
+    FixedUpdate,
+    /// Executed right after the scene's update. Scion's internal systems run in this stage.
+    PreUpdate,
+    /// Default stage of the systems.
+    Update,
+    /// Executed after the `Update` stage, before the scene's late update.
+    PostUpdate,
+    /// Last stage of the frame, executed after the scene's late update, before the rendering.
+    PreRender,
+}
+
+impl Stage {
+    pub(crate) const ALL: [Stage; 5] = [Stage::FixedUpdate, Stage::PreUpdate, Stage::Update, Stage::PostUpdate, Stage::PreRender];
+}
+
+/// How a system is executed by the scheduler
+pub(crate) enum SystemRunner {
+    /// Runs alone, with an exclusive access to the `GameData`
+    Exclusive(Box<dyn System>),
+    /// Runs concurrently with other non conflicting parallel systems
+    Parallel(Box<dyn ParallelSystem>, SystemAccess),
+}
+
+impl SystemRunner {
+    fn on_init(&mut self, data: &mut GameData) {
+        match self {
+            SystemRunner::Exclusive(system) => system.on_init(data),
+            SystemRunner::Parallel(system, _) => system.on_init(data),
+        }
+    }
+
+    fn on_shutdown(&mut self, data: &mut GameData) {
+        match self {
+            SystemRunner::Exclusive(system) => system.on_shutdown(data),
+            SystemRunner::Parallel(system, _) => system.on_shutdown(data),
+        }
+    }
+}
+
+/// `SystemConfig` describes how a system must be scheduled : its stage, its label and its ordering constraints.
+/// ```no_run
+/// use scion::core::scheduler::{Stage, SystemConfig};
+/// use scion::core::world::GameData;
+///
+/// fn gravity_system(_data: &mut GameData) {}
+///
+/// let config = SystemConfig::new(gravity_system)
+///     .in_stage(Stage::PreUpdate)
+///     .label("gravity")
+///     .after("compute_collisions_system")
+///     .before("dirty_transform_system");
+/// ```
+pub struct SystemConfig {
+    pub(crate) runner: SystemRunner,
+    pub(crate) name: &'static str,
+    pub(crate) stage: Stage,
+    pub(crate) label: Option<String>,
+    pub(crate) before: Vec<String>,
+    pub(crate) after: Vec<String>,
+    pub(crate) run_criteria: Option<RunCriteria>,
+}
+
+impl SystemConfig {
+    /// Creates a new configuration for `system`, scheduled in the `Update` stage
+    pub fn new<S: System>(system: S) -> Self {
+        Self::with_runner(SystemRunner::Exclusive(Box::new(system)), std::any::type_name::<S>())
+    }
+
+    /// Creates a new configuration for a parallel `system`, scheduled in the `Update` stage.
+    /// `access` must declare every component and resource used by the system.
+    pub fn parallel<S: ParallelSystem>(system: S, access: SystemAccess) -> Self {
+        Self::with_runner(SystemRunner::Parallel(Box::new(system), access), std::any::type_name::<S>())
+    }
+
+    fn with_runner(runner: SystemRunner, name: &'static str) -> Self {
+        Self {
+            runner,
+            name,
+            stage: Stage::Update,
+            label: None,
+            before: vec![],
+            after: vec![],
+            run_criteria: None,
+        }
+    }
+
+    /// Sets the stage in which the system will be executed
+    pub fn in_stage(mut self, stage: Stage) -> Self {
+        self.stage = stage;
+        self
+    }
+
+    /// Sets the label of this system, used by other systems to declare their ordering constraints
+    pub fn label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// The system will be executed before every system labelled `label`
+    pub fn before(mut self, label: &str) -> Self {
+        self.before.push(label.to_string());
+        self
+    }
+
+    /// The system will be executed after every system labelled `label`
+    pub fn after(mut self, label: &str) -> Self {
+        self.after.push(label.to_string());
+        self
+    }
+
+    /// The system will only be executed when `criteria` is met. Can be called several times, all the criteria must then be met.
+    pub fn run_if(mut self, criteria: RunCriteria) -> Self {
+        self.run_criteria = Some(match self.run_criteria.take() {
+            None => criteria,
+            Some(current) => current.and(criteria),
+        });
+        self
+    }
+
+    /// The system won't be executed while `pause_condition` returns true
+    pub fn with_pause_condition(self, pause_condition: fn(&GameState) -> bool) -> Self {
+        self.run_if(!RunCriteria::game_state(pause_condition))
+    }
+
+    /// Name used to identify this system in errors
+    pub(crate) fn display_name(&self) -> &str {
+        self.label.as_deref().unwrap_or(self.name)
+    }
+
+    /// Returns whether or not this system and `other` can be executed at the same time
+    fn can_run_with(&self, other: &SystemConfig) -> bool {
+        let constrains = |a: &SystemConfig, b: &SystemConfig| {
+            b.label.as_ref().is_some_and(|label| a.before.contains(label) || a.after.contains(label))
+        };
+        match (&self.runner, &other.runner) {
+            (SystemRunner::Parallel(_, access), SystemRunner::Parallel(_, other_access)) => {
+                self.stage == other.stage
+                    && !access.conflicts_with(other_access)
+                    && !constrains(self, other)
+                    && !constrains(other, self)
+            }
+            _ => false,
+        }
+    }
+}
+
+/// `SchedulerError` represents the different errors that can happen when resolving the systems order
+#[derive(Debug)]
+pub enum SchedulerError {
+    /// An ordering constraint references a label that no system uses
+    UnknownLabel { system: String, label: String },
+    /// An ordering constraint references a label used in a stage that makes it impossible to respect
+    StageConflict { system: String, label: String },
+    /// Ordering constraints of the listed systems are cycling
+    Cycle { stage: Stage, systems: Vec<String> },
+}
+
+impl Display for SchedulerError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchedulerError::UnknownLabel { system, label } => {
+                write!(f, "System {} has an ordering constraint on unknown label '{}'", system, label)
+            }
+            SchedulerError::StageConflict { system, label } => {
+                write!(f, "System {} has an ordering constraint on '{}' that is impossible to respect due to their stages", system, label)
+            }
+            SchedulerError::Cycle { stage, systems } => {
+                write!(f, "Cycle detected in stage {:?} between systems [{}]", stage, systems.join(", "))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Scheduler {
+    systems: Vec<SystemConfig>,
+    /// Groups of consecutive systems that can be executed at the same time
+    batches: Vec<Range<usize>>,
+    mode: ExecutionMode,
+}
+
+impl Scheduler {
+    pub(crate) fn add_system<S: System>(&mut self, system: S) {
+        self.systems.push(SystemConfig::new(system));
+    }
+
+    pub(crate) fn add_pausable_system<S: System>(&mut self,
+                                                 system: S,
+                                                 pause_condition: fn(&GameState) -> bool) {
+        self.systems.push(SystemConfig::new(system).with_pause_condition(pause_condition));
+    }
+
+    pub(crate) fn add_system_config(&mut self, config: SystemConfig) {
+        self.systems.push(config);
+    }
+
+    pub(crate) fn set_execution_mode(&mut self, mode: ExecutionMode) {
+        self.mode = mode;
+    }
+
+    /// Reorders the systems following their stages and ordering constraints, then groups the
+    /// consecutive parallel systems that can be executed at the same time.
+    pub(crate) fn resolve_order(&mut self) -> Result<(), SchedulerError> {
+        let order = ordering::resolve(&self.systems)?;
+        let mut systems: Vec<Option<SystemConfig>> = self.systems.drain(0..).map(Some).collect();
+        self.systems = order.iter().map(|index| systems[*index].take().expect("A system has been ordered twice")).collect();
+
+        self.batches.clear();
+        let mut start = 0;
+        for index in 1..=self.systems.len() {
+            if index == self.systems.len()
+                || !self.systems[start..index].iter().all(|s| s.can_run_with(&self.systems[index])) {
+                self.batches.push(start..index);
+                start = index;
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn init(&mut self, data: &mut GameData) {
+        self.systems.iter_mut().for_each(|s| s.runner.on_init(data));
+    }
+
+    /// Executes the `PreUpdate`, `Update` and `PostUpdate` stages
+    pub(crate) fn execute(&mut self, data: &mut GameData) {
+        self.execute_stage(Stage::PreUpdate, data);
+        self.execute_stage(Stage::Update, data);
+        self.execute_stage(Stage::PostUpdate, data);
+    }
+
+    pub(crate) fn execute_stage(&mut self, stage: Stage, data: &mut GameData) {
+        let active: Vec<bool> = self
+            .systems
+            .iter_mut()
+            .map(|s| s.stage == stage && s.run_criteria.as_mut().is_none_or(|criteria| criteria.should_run(data)))
+            .collect();
+
+        for batch in self.batches.iter() {
+            let mut systems: Vec<&mut SystemConfig> = self.systems[batch.clone()]
+                .iter_mut()
+                .zip(&active[batch.clone()])
+                .filter(|(_, active)| **active)
+                .map(|(s, _)| s)
+                .collect();
+
+            if self.mode == ExecutionMode::Parallel && systems.len() > 1 {
+                // Executed by the threads of the rayon pool, kept alive between the batches
+                let shared: &GameData = data;
+                rayon::scope(|scope| {
+                    systems.drain(0..).for_each(|s| {
+                        if let SystemRunner::Parallel(system, _) = &mut s.runner {
+                            scope.spawn(move |_| system.run(shared));
+                        }
+                    });
+                });
+            } else {
+                systems.drain(0..).for_each(|s| match &mut s.runner {
+                    SystemRunner::Exclusive(system) => system.run(data),
+                    SystemRunner::Parallel(system, _) => system.run(data),
+                });
+            }
+        }
+        apply_commands(data);
+        data.flush_lifecycle_events();
+    }
+
+    pub(crate) fn shutdown(&mut self, data: &mut GameData) {
+        self.systems.iter_mut().for_each(|s| s.runner.on_shutdown(data));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Counter(usize);
+
+    struct CountingSystem {
+        step: usize,
+    }
+
+    impl System for CountingSystem {
+        fn run(&mut self, data: &mut GameData) {
+            data.get_resource_mut::<Counter>().unwrap().0 += self.step;
+        }
+
+        fn on_init(&mut self, _data: &mut GameData) {
+            self.step = 2;
+        }
+
+        fn on_shutdown(&mut self, data: &mut GameData) {
+            data.get_resource_mut::<Counter>().unwrap().0 = 0;
+        }
+    }
+
+    #[test]
+    fn stateful_system_test() {
+        let mut data = GameData::default();
+        data.insert_resource(GameState::default());
+        data.insert_resource(Counter::default());
+
+        let mut calls = 0;
+        let mut scheduler = Scheduler::default();
+        scheduler.add_system(CountingSystem { step: 1 });
+        scheduler.add_system(move |data: &mut GameData| {
+            calls += 1;
+            data.get_resource_mut::<Counter>().unwrap().0 += calls;
+        });
+
+        scheduler.resolve_order().unwrap();
+        scheduler.init(&mut data);
+        scheduler.execute(&mut data);
+        scheduler.execute(&mut data);
+        assert_eq!(7, data.get_resource::<Counter>().unwrap().0);
+
+        scheduler.shutdown(&mut data);
+        assert_eq!(0, data.get_resource::<Counter>().unwrap().0);
+    }
+
+    #[test]
+    fn pausable_system_test() {
+        let mut data = GameData::default();
+        data.insert_resource(GameState::default());
+        data.insert_resource(Counter::default());
+
+        let mut scheduler = Scheduler::default();
+        scheduler.add_pausable_system(CountingSystem { step: 1 }, |gs| gs.get_bool("pause"));
+        scheduler.resolve_order().unwrap();
+
+        scheduler.execute(&mut data);
+        data.game_state_mut().set_bool("pause", true);
+        scheduler.execute(&mut data);
+        assert_eq!(1, data.get_resource::<Counter>().unwrap().0);
+    }
+
+    #[test]
+    fn execution_follows_stages_and_constraints_test() {
+        #[derive(Default)]
+        struct Trace(Vec<&'static str>);
+
+        fn tracing(name: &'static str) -> impl FnMut(&mut GameData) + Send {
+            move |data: &mut GameData| data.get_resource_mut::<Trace>().unwrap().0.push(name)
+        }
+
+        let mut data = GameData::default();
+        data.insert_resource(GameState::default());
+        data.insert_resource(Trace::default());
+
+        let mut scheduler = Scheduler::default();
+        scheduler.add_system(tracing("update"));
+        scheduler.add_system_config(SystemConfig::new(tracing("post")).in_stage(Stage::PostUpdate));
+        scheduler.add_system_config(SystemConfig::new(tracing("b")).in_stage(Stage::PreUpdate).label("b"));
+        scheduler.add_system_config(SystemConfig::new(tracing("a")).in_stage(Stage::PreUpdate).label("a").before("b"));
+        scheduler.add_system_config(SystemConfig::new(tracing("render")).in_stage(Stage::PreRender));
+        scheduler.resolve_order().unwrap();
+
+        scheduler.execute(&mut data);
+        assert_eq!(vec!["a", "b", "update", "post"], data.get_resource::<Trace>().unwrap().0);
+        scheduler.execute_stage(Stage::PreRender, &mut data);
+        assert_eq!("render", *data.get_resource::<Trace>().unwrap().0.last().unwrap());
+    }
+
+    #[test]
+    fn parallel_systems_are_batched_test() {
+        use crate::core::components::maths::transform::Transform;
+        use crate::core::resources::time::Time;
+
+        let mut scheduler = Scheduler::default();
+        scheduler.add_system_config(SystemConfig::parallel(|_: &GameData| {}, SystemAccess::default().read::<Transform>()));
+        scheduler.add_system_config(SystemConfig::parallel(|_: &GameData| {}, SystemAccess::default().read::<Transform>().write_resource::<Time>()));
+        scheduler.add_system_config(SystemConfig::parallel(|_: &GameData| {}, SystemAccess::default().read_resource::<Time>()));
+        scheduler.add_system(|_: &mut GameData| {});
+        scheduler.add_system_config(SystemConfig::parallel(|_: &GameData| {}, SystemAccess::default()).label("a"));
+        scheduler.add_system_config(SystemConfig::parallel(|_: &GameData| {}, SystemAccess::default()).after("a"));
+        scheduler.resolve_order().unwrap();
+
+        assert_eq!(vec![0..2, 2..3, 3..4, 4..5, 5..6], scheduler.batches);
+    }
+
+    #[test]
+    fn game_data_can_be_shared_with_parallel_systems_test() {
+        fn assert_sync<T: Send + Sync>() {}
+        assert_sync::<GameData>();
+    }
+
+    #[test]
+    fn parallel_and_single_threaded_execution_test() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Sum(AtomicUsize);
+
+        for mode in [ExecutionMode::Parallel, ExecutionMode::SingleThreaded] {
+            let mut data = GameData::default();
+            data.insert_resource(GameState::default());
+            data.insert_resource(Sum::default());
+
+            let mut scheduler = Scheduler::default();
+            scheduler.set_execution_mode(mode);
+            for i in 1..=4 {
+                scheduler.add_system_config(SystemConfig::parallel(
+                    move |data: &GameData| {
+                        data.get_resource::<Sum>().unwrap().0.fetch_add(i, Ordering::SeqCst);
+                    },
+                    SystemAccess::default().read_resource::<Sum>(),
+                ));
+            }
+            scheduler.resolve_order().unwrap();
+            assert_eq!(1, scheduler.batches.len());
+
+            scheduler.execute(&mut data);
+            assert_eq!(10, data.get_resource::<Sum>().unwrap().0.load(Ordering::SeqCst));
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::{BTreeSet, HashMap};
+
+use crate::core::scheduler::{SchedulerError, Stage, SystemConfig};
+
+/// Computes the execution order of `systems`, as a list of indexes.
+/// Systems are grouped by stage, then topologically sorted inside their stage following their
+/// `before` / `after` constraints. When nothing constrains two systems, the registration order is kept.
+pub(crate) fn resolve(systems: &[SystemConfig]) -> Result<Vec<usize>, SchedulerError> {
+    let mut labels: HashMap<&str, Vec<usize>> = HashMap::new();
+    systems.iter().enumerate().filter(|(_, s)| s.label.is_some()).for_each(|(index, s)| {
+        labels.entry(s.label.as_deref().unwrap()).or_default().push(index);
+    });
+
+    // edges[a] contains b when a must run before b
+    let mut edges: Vec<BTreeSet<usize>> = vec![BTreeSet::new(); systems.len()];
+    for (index, system) in systems.iter().enumerate() {
+        for label in system.before.iter() {
+            for target in targets(systems, &labels, index, label, |s, t| s <= t)? {
+                edges[index].insert(target);
+            }
+        }
+        for label in system.after.iter() {
+            for target in targets(systems, &labels, index, label, |s, t| s >= t)? {
+                edges[target].insert(index);
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(systems.len());
+    for stage in Stage::ALL.iter() {
+        order.append(&mut sort_stage(systems, &edges, *stage)?);
+    }
+    Ok(order)
+}
+
+/// Returns the systems of the same stage as `index` that are labelled `label`, after checking
+/// that the other labelled systems are in a stage compatible with the constraint.
+fn targets(
+    systems: &[SystemConfig],
+    labels: &HashMap<&str, Vec<usize>>,
+    index: usize,
+    label: &str,
+    compatible_stages: fn(Stage, Stage) -> bool,
+) -> Result<Vec<usize>, SchedulerError> {
+    let system = &systems[index];
+    let labelled = labels.get(label).ok_or_else(|| SchedulerError::UnknownLabel {
+        system: system.display_name().to_string(),
+        label: label.to_string(),
+    })?;
+    let incompatible = |target: Stage| {
+        target != system.stage
+            && (target == Stage::FixedUpdate || system.stage == Stage::FixedUpdate || !compatible_stages(system.stage, target))
+    };
+    if labelled.iter().any(|t| incompatible(systems[*t].stage)) {
+        return Err(SchedulerError::StageConflict {
+            system: system.display_name().to_string(),
+            label: label.to_string(),
+        });
+    }
+    Ok(labelled.iter().filter(|t| **t != index && systems[**t].stage == system.stage).copied().collect())
+}
+
+fn sort_stage(systems: &[SystemConfig], edges: &[BTreeSet<usize>], stage: Stage) -> Result<Vec<usize>, SchedulerError> {
+    let members: Vec<usize> = (0..systems.len()).filter(|i| systems[*i].stage == stage).collect();
+    let mut incoming: HashMap<usize, usize> = members.iter().map(|i| (*i, 0)).collect();
+    members.iter().flat_map(|i| edges[*i].iter()).for_each(|target| {
+        *incoming.get_mut(target).expect("An ordering constraint crosses stages") += 1;
+    });
+
+    let mut available: BTreeSet<usize> = members.iter().filter(|i| incoming[*i] == 0).copied().collect();
+    let mut sorted = Vec::with_capacity(members.len());
+    while let Some(next) = available.pop_first() {
+        sorted.push(next);
+        for target in edges[next].iter() {
+            let count = incoming.get_mut(target).expect("An ordering constraint crosses stages");
+            *count -= 1;
+            if *count == 0 {
+                available.insert(*target);
+            }
+        }
+    }
+
+    if sorted.len() != members.len() {
+        return Err(SchedulerError::Cycle {
+            stage,
+            systems: members
+                .iter()
+                .filter(|i| incoming[*i] > 0)
+                .map(|i| systems[*i].display_name().to_string())
+                .collect(),
+        });
+    }
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::world::GameData;
+
+    use super::*;
+
+    fn system(label: &str) -> SystemConfig {
+        SystemConfig::new(|_: &mut GameData| {}).label(label)
+    }
+
+    #[test]
+    fn registration_order_is_kept_without_constraints_test() {
+        let systems = vec![system("a"), system("b"), system("c")];
+        assert_eq!(vec![0, 1, 2], resolve(&systems).unwrap());
+    }
+
+    #[test]
+    fn constraints_reorder_systems_test() {
+        let systems = vec![system("a").after("c"), system("b"), system("c").before("b")];
+        assert_eq!(vec![2, 0, 1], resolve(&systems).unwrap());
+    }
+
+    #[test]
+    fn stages_are_ordered_test() {
+        let systems = vec![
+            system("a").in_stage(Stage::PreRender),
+            system("b").in_stage(Stage::PostUpdate).after("c"),
+            system("c").in_stage(Stage::PreUpdate),
+        ];
+        assert_eq!(vec![2, 1, 0], resolve(&systems).unwrap());
+    }
+
+    #[test]
+    fn cycle_is_reported_test() {
+        let systems = vec![system("a").after("b"), system("b").after("a"), system("c")];
+        match resolve(&systems) {
+            Err(SchedulerError::Cycle { stage, systems }) => {
+                assert_eq!(Stage::Update, stage);
+                assert_eq!(vec!["a".to_string(), "b".to_string()], systems);
+            }
+            _ => panic!("A cycle should have been detected"),
+        }
+    }
+
+    #[test]
+    fn invalid_labels_are_reported_test() {
+        assert!(matches!(resolve(&[system("a").after("unknown")]), Err(SchedulerError::UnknownLabel { .. })));
+        let systems = vec![system("a").after("b"), system("b").in_stage(Stage::PostUpdate)];
+        assert!(matches!(resolve(&systems), Err(SchedulerError::StageConflict { .. })));
+        let systems = vec![system("a").before("b"), system("b").in_stage(Stage::FixedUpdate)];
+        assert!(matches!(resolve(&systems), Err(SchedulerError::StageConflict { .. })));
+    }
+}
@@ -0,0 +1,109 @@
+use std::any::TypeId;
+use std::collections::HashSet;
+
+use hecs::Component;
+
+use crate::core::world::{GameData, Resource, ResourceTypeId};
+
+/// Trait to implement in order to define a system that can be executed concurrently with other systems.
+/// Unlike [`crate::core::scheduler::System`], it only has a shared access to the [`GameData`], so it
+/// can't do structural changes on the world. Any `FnMut(&GameData)` function or closure is already a `ParallelSystem`.
+/// Resources must be retrieved with [`GameData::get_resource`] or [`GameData::get_resource_mut`], following the
+/// declared [`SystemAccess`] : convenience accessors like `inputs()` or `timers()` borrow the resource
+/// mutably, and panic if another system reads it at the same time.
+pub trait ParallelSystem: Send + 'static {
+    /// Will be called each game loop, by the scheduler, possibly on another thread.
+    fn run(&mut self, data: &GameData);
+    /// Will be called once, before the first scene start. Useful to create subscriptions or resources.
+    fn on_init(&mut self, _data: &mut GameData) {}
+    /// Will be called once, when the game loop stops.
+    fn on_shutdown(&mut self, _data: &mut GameData) {}
+}
+
+impl<F> ParallelSystem for F
+where
+    F: FnMut(&GameData) + Send + 'static,
+{
+    fn run(&mut self, data: &GameData) {
+        self(data)
+    }
+}
+
+/// `SystemAccess` declares the components and resources a [`ParallelSystem`] reads and writes.
+/// Two systems are executed concurrently only if none of them writes something the other one accesses.
+/// Accessing something that has not been declared will panic if another system is using it at the same time.
+#[derive(Default, Debug, Clone)]
+pub struct SystemAccess {
+    read_components: HashSet<TypeId>,
+    write_components: HashSet<TypeId>,
+    read_resources: HashSet<ResourceTypeId>,
+    write_resources: HashSet<ResourceTypeId>,
+}
+
+impl SystemAccess {
+    /// Declares a read access on the component `T`
+    pub fn read<T: Component>(mut self) -> Self {
+        self.read_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Declares a write access on the component `T`
+    pub fn write<T: Component>(mut self) -> Self {
+        self.write_components.insert(TypeId::of::<T>());
+        self
+    }
+
+    /// Declares a read access on the resource `T`, to retrieve with [`GameData::get_resource`]
+    pub fn read_resource<T: Resource>(mut self) -> Self {
+        self.read_resources.insert(ResourceTypeId::of::<T>());
+        self
+    }
+
+    /// Declares a write access on the resource `T`
+    pub fn write_resource<T: Resource>(mut self) -> Self {
+        self.write_resources.insert(ResourceTypeId::of::<T>());
+        self
+    }
+
+    /// Returns whether or not the two accesses can't be executed at the same time
+    pub(crate) fn conflicts_with(&self, other: &SystemAccess) -> bool {
+        fn overlaps<T: Eq + std::hash::Hash>(writes: &HashSet<T>, other_reads: &HashSet<T>, other_writes: &HashSet<T>) -> bool {
+            writes.iter().any(|t| other_reads.contains(t) || other_writes.contains(t))
+        }
+        overlaps(&self.write_components, &other.read_components, &other.write_components)
+            || overlaps(&other.write_components, &self.read_components, &self.write_components)
+            || overlaps(&self.write_resources, &other.read_resources, &other.write_resources)
+            || overlaps(&other.write_resources, &self.read_resources, &self.write_resources)
+    }
+}
+
+/// `ExecutionMode` defines how the scheduler executes the parallel systems
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub enum ExecutionMode {
+    /// Non conflicting parallel systems are executed concurrently
+    #[default]
+    Parallel,
+    /// Every system is executed on the game thread, following the resolved order. Useful to debug.
+    SingleThreaded,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::components::maths::transform::Transform;
+    use crate::core::resources::time::Time;
+
+    use super::*;
+
+    #[test]
+    fn access_conflicts_test() {
+        let read_transform = SystemAccess::default().read::<Transform>().read_resource::<Time>();
+        let write_transform = SystemAccess::default().write::<Transform>();
+        let write_time = SystemAccess::default().write_resource::<Time>();
+
+        assert!(!read_transform.conflicts_with(&read_transform));
+        assert!(read_transform.conflicts_with(&write_transform));
+        assert!(write_transform.conflicts_with(&read_transform));
+        assert!(read_transform.conflicts_with(&write_time));
+        assert!(!write_transform.conflicts_with(&write_time));
+    }
+}
@@ -0,0 +1,197 @@
+use std::ops::Not;
+use std::time::Duration;
+
+use crate::core::resources::events::{PollConfiguration, SubscriberId};
+use crate::core::resources::time::Time;
+use crate::core::scene::Scene;
+use crate::core::state::GameState;
+use crate::core::world::GameData;
+
+/// `RunCriteria` decides, each time its stage is executed, whether or not a system must run.
+/// Criteria can be combined using [`RunCriteria::and`], [`RunCriteria::or`] and `!`.
+/// ```no_run
+/// use std::time::Duration;
+/// use scion::core::scene::Scene;
+/// use scion::core::scheduler::RunCriteria;
+///
+/// #[derive(Default)]
+/// struct Level;
+/// impl Scene for Level {}
+///
+/// let criteria = RunCriteria::in_scene::<Level>()
+///     .and(RunCriteria::every(Duration::from_millis(500)))
+///     .and(!RunCriteria::game_state(|gs| gs.get_bool("pause")));
+/// ```
+pub struct RunCriteria {
+    predicate: Box<dyn FnMut(&GameData) -> bool + Send>,
+}
+
+impl RunCriteria {
+    /// Creates a criteria from a custom predicate
+    pub fn new<F: FnMut(&GameData) -> bool + Send + 'static>(predicate: F) -> Self {
+        Self { predicate: Box::new(predicate) }
+    }
+
+    /// Runs while `condition` returns true on the current `GameState`
+    pub fn game_state(condition: fn(&GameState) -> bool) -> Self {
+        Self::new(move |data| condition(&data.game_state()))
+    }
+
+    /// Runs only while a scene of type `T` is updated, see [`crate::core::scene::SceneController::is_active`]
+    pub fn in_scene<T: Scene>() -> Self {
+        Self::new(|data| data.scene_controller().is_active::<T>())
+    }
+
+    /// Runs at most once every `interval`, measured with the game [`Time`]. It follows the time scale, so it
+    /// stops while the game is paused : use [`RunCriteria::every_unscaled`] for menus or UI.
+    pub fn every(interval: Duration) -> Self {
+        Self::every_with(interval, Time::total_duration)
+    }
+
+    /// Runs at most once every `interval` of real time, ignoring the time scale and the pause
+    pub fn every_unscaled(interval: Duration) -> Self {
+        Self::every_with(interval, Time::unscaled_total_duration)
+    }
+
+    fn every_with(interval: Duration, elapsed: fn(&Time) -> Duration) -> Self {
+        let mut next_run = Duration::ZERO;
+        Self::new(move |data| {
+            let total = elapsed(&data.get_resource::<Time>().expect("Time is an internal resource and can't be missing"));
+            if total < next_run {
+                return false;
+            }
+            next_run = total + interval;
+            true
+        })
+    }
+
+    /// Runs when messages have been published in the topic `topic_name` since the last evaluation.
+    /// Messages published before the first evaluation are ignored.
+    pub fn on_event(topic_name: &str) -> Self {
+        let topic_name = topic_name.to_string();
+        let mut subscriber: Option<SubscriberId> = None;
+        Self::new(move |data| {
+            let mut events = data.events();
+            match subscriber {
+                None => {
+                    subscriber = events.subscribe(&topic_name, PollConfiguration::default()).ok();
+                    if let Some(id) = subscriber {
+                        let _r = events.set_persistent(&id);
+                    }
+                    false
+                }
+                Some(id) => events.mark_read(&id).is_ok_and(|unread| unread > 0),
+            }
+        })
+    }
+
+    /// Runs only the first time it is evaluated
+    pub fn once() -> Self {
+        let mut done = false;
+        Self::new(move |_| !std::mem::replace(&mut done, true))
+    }
+
+    /// Runs when both criteria are met. `other` is not evaluated if `self` is not met.
+    pub fn and(mut self, mut other: RunCriteria) -> Self {
+        Self::new(move |data| self.should_run(data) && other.should_run(data))
+    }
+
+    /// Runs when one of the criteria is met. `other` is not evaluated if `self` is met.
+    pub fn or(mut self, mut other: RunCriteria) -> Self {
+        Self::new(move |data| self.should_run(data) || other.should_run(data))
+    }
+
+    pub(crate) fn should_run(&mut self, data: &GameData) -> bool {
+        (self.predicate)(data)
+    }
+}
+
+impl Not for RunCriteria {
+    type Output = RunCriteria;
+
+    /// Runs when the criteria is not met
+    fn not(mut self) -> Self::Output {
+        Self::new(move |data| !self.should_run(data))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::resources::events::Events;
+    use crate::core::resources::events::topic::TopicConfiguration;
+    use std::any::TypeId;
+
+    use crate::core::scene::SceneController;
+
+    use super::*;
+
+    fn game_data() -> GameData {
+        let mut data = GameData::default();
+        data.insert_resource(GameState::default());
+        data.insert_resource(Time::default());
+        data.insert_resource(Events::default());
+        data.insert_resource(SceneController::default());
+        data
+    }
+
+    #[test]
+    fn once_and_combinators_test() {
+        let data = game_data();
+        let mut once = RunCriteria::once();
+        assert!(once.should_run(&data));
+        assert!(!once.should_run(&data));
+
+        let mut criteria = RunCriteria::once().or(RunCriteria::game_state(|gs| gs.get_bool("flag")));
+        assert!(criteria.should_run(&data));
+        assert!(!criteria.should_run(&data));
+        data.game_state_mut().set_bool("flag", true);
+        assert!(criteria.should_run(&data));
+        assert!(!(!RunCriteria::game_state(|gs| gs.get_bool("flag"))).should_run(&data));
+        assert!(!RunCriteria::once().and(RunCriteria::new(|_| false)).should_run(&data));
+    }
+
+    #[test]
+    fn every_test() {
+        let data = game_data();
+        let mut criteria = RunCriteria::every(Duration::from_millis(100));
+        assert!(criteria.should_run(&data));
+        data.get_resource_mut::<Time>().unwrap().advance(Duration::from_millis(60));
+        assert!(!criteria.should_run(&data));
+        data.get_resource_mut::<Time>().unwrap().advance(Duration::from_millis(60));
+        assert!(criteria.should_run(&data));
+
+        let mut unscaled = RunCriteria::every_unscaled(Duration::from_millis(100));
+        assert!(unscaled.should_run(&data));
+        data.get_resource_mut::<Time>().unwrap().pause();
+        data.get_resource_mut::<Time>().unwrap().advance(Duration::from_millis(120));
+        assert!(unscaled.should_run(&data));
+        assert!(!criteria.should_run(&data));
+    }
+
+    #[test]
+    fn on_event_test() {
+        let data = game_data();
+        let _r = data.events().create_topic("topic", TopicConfiguration::default());
+        let mut criteria = RunCriteria::on_event("topic");
+        assert!(!criteria.should_run(&data));
+        let _r = data.events().publish("topic", 1);
+        assert!(criteria.should_run(&data));
+        assert!(!criteria.should_run(&data));
+    }
+
+    #[test]
+    fn in_scene_test() {
+        struct A;
+        impl Scene for A {}
+        struct B;
+        impl Scene for B {}
+
+        let data = game_data();
+        let mut criteria = RunCriteria::in_scene::<A>();
+        assert!(!criteria.should_run(&data));
+        data.scene_controller().set_scene_types(Some(TypeId::of::<A>()), vec![TypeId::of::<A>()]);
+        assert!(criteria.should_run(&data));
+        data.scene_controller().set_scene_types(Some(TypeId::of::<B>()), vec![TypeId::of::<B>()]);
+        assert!(!criteria.should_run(&data));
+    }
+}
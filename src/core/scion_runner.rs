@@ -1,14 +1,17 @@
 use std::sync::{Arc, mpsc};
 use std::sync::mpsc::Receiver;
 use std::thread;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use log::info;
 use winit::dpi::{PhysicalSize, Size};
+use winit::event_loop::EventLoopProxy;
 use winit::window::Window;
 
-use crate::core::resources::time::Time;
+use crate::core::resources::random::Random;
+use crate::core::resources::time::{Time, TimerFinished};
 use crate::core::scene::{SceneAction, SceneMachine};
-use crate::core::scheduler::Scheduler;
+use crate::core::scheduler::{Scheduler, Stage};
 use crate::core::world::GameData;
 use crate::graphics::rendering::{RendererEvent, RenderingInfos, RenderingUpdate};
 use crate::graphics::rendering::scion2d::pre_renderer::Scion2DPreRenderer;
@@ -26,44 +29,46 @@ pub struct ScionRunner {
     pub(crate) window: Option<Arc<Window>>,
     pub(crate) main_thread_receiver: Option<Receiver<WindowingEvent>>,
     pub(crate) scion_pre_renderer: Scion2DPreRenderer,
+    pub(crate) running: bool,
+    /// Used to close the main thread event loop when the game loop stops
+    pub(crate) event_loop_proxy: Option<EventLoopProxy<()>>,
 }
 
 impl ScionRunner {
     pub(crate) fn launch_game_loop(mut self) {
         self.setup();
-        let mut frame_limiter = FrameLimiter::new(FrameLimiterConfig::default());
+        let fixed_delta_duration = self
+            .game_data
+            .get_resource::<Time>()
+            .expect("Time is an internal resource and can't be missing")
+            .fixed_delta_duration();
+        let mut frame_limiter = FrameLimiter::new(FrameLimiterConfig::default(), fixed_delta_duration);
         let (render_sender, render_receiver) = mpsc::channel::<(Vec<RendererEvent>, Vec<RenderingUpdate>, Vec<RenderingInfos>)>();
         let window_rendering_manager = self.window_rendering_manager.take();
 
         thread::spawn(move || { ScionRenderingThread::new(window_rendering_manager, render_receiver).run() });
 
         let mut start_tick = Instant::now();
-        let mut fixed_tick = Instant::now();
         let mut render_tick = Instant::now();
 
-        loop {
+        while self.running {
             let should_tick = frame_limiter.is_min_tick();
             if should_tick {
                 start_tick = Instant::now();
-                let frame_duration = self
+                let measured_duration = self
                     .game_data
                     .get_resource_mut::<Time>()
                     .expect("Time is an internal resource and can't be missing")
-                    .frame();
-                self.game_data.timers().add_delta_duration(frame_duration);
+                    .measure();
+                let frame_duration = self.advance_time(measured_duration);
                 let _r = render_sender.send((handle_window_event(&mut self), vec![], vec![]));
-                self.layer_machine.apply_scene_action(SceneAction::Update, &mut self.game_data);
-                self.scheduler.execute(&mut self.game_data);
-                self.layer_machine.apply_scene_action(SceneAction::LateUpdate, &mut self.game_data);
+                self.update(frame_duration);
+                for _ in 0..frame_limiter.accumulate_fixed_steps(frame_duration) {
+                    self.fixed_update();
+                }
                 self.update_cursor();
             }
 
-            if frame_limiter.is_fixed_update() {
-                fixed_tick = Instant::now();
-                self.layer_machine.apply_scene_action(SceneAction::FixedUpdate, &mut self.game_data);
-                frame_limiter.fixed_tick();
-            }
-
             if frame_limiter.render_unlocked() {
                 render_tick = Instant::now();
 
@@ -75,31 +80,108 @@ impl ScionRunner {
             }
 
             if should_tick {
-                self.game_data.inputs().reset_inputs();
-                self.game_data.events().cleanup();
-                self.layer_machine
-                    .apply_scene_action(SceneAction::EndFrame, &mut self.game_data);
+                self.end_frame();
                 frame_limiter.tick(&start_tick);
             }
             thread::sleep(frame_limiter.min_tick_duration.clone());
         }
+        self.shutdown();
     }
 
     pub(crate) fn setup(&mut self) {
-        self.game_data.insert_resource(crate::core::resources::window::Window::new(
-            (self.window.as_ref().unwrap().inner_size().width, self.window.as_ref().unwrap().inner_size().height),
-            self.window.as_ref().unwrap().scale_factor(),
-        ));
+        if let Some(window) = self.window.as_ref() {
+            self.game_data.insert_resource(crate::core::resources::window::Window::new(
+                (window.inner_size().width, window.inner_size().height),
+                window.scale_factor(),
+            ));
+        } else if !self.game_data.contains_resource::<crate::core::resources::window::Window>() {
+            self.game_data.insert_resource(crate::core::resources::window::Window::new((0, 0), 1.));
+        }
+        self.scheduler.init(&mut self.game_data);
         self.layer_machine.apply_scene_action(SceneAction::Start, &mut self.game_data);
     }
+
+    /// Starts a new frame lasting `measured_duration`, or the recorded duration when replaying inputs,
+    /// and returns its scaled duration
+    pub(crate) fn advance_time(&mut self, measured_duration: Duration) -> Duration {
+        let frame_duration = {
+            let mut inputs = self.game_data.inputs();
+            if let Some(mut random) = self.game_data.get_resource_mut::<Random>() {
+                inputs.sync_random(&mut random);
+            }
+            inputs.frame_duration(measured_duration)
+        };
+        self.game_data
+            .get_resource_mut::<Time>()
+            .expect("Time is an internal resource and can't be missing")
+            .advance(frame_duration)
+    }
+
+    /// Executes the frame update : scene update, systems and scene late update
+    pub(crate) fn update(&mut self, frame_duration: Duration) {
+        self.game_data.inputs().start_frame();
+        self.advance_clocks(frame_duration);
+        self.layer_machine.apply_scene_action(SceneAction::Update, &mut self.game_data);
+        self.scheduler.execute(&mut self.game_data);
+        self.layer_machine.apply_scene_action(SceneAction::LateUpdate, &mut self.game_data);
+        self.scheduler.execute_stage(Stage::PreRender, &mut self.game_data);
+    }
+
+    /// Advances the named timers and the scheduled events, and notifies the finished timers
+    fn advance_clocks(&mut self, frame_duration: Duration) {
+        let unscaled_duration = self
+            .game_data
+            .get_resource::<Time>()
+            .expect("Time is an internal resource and can't be missing")
+            .unscaled_delta_duration();
+        let finished_timers = self.game_data.timers().add_delta_duration(frame_duration, unscaled_duration);
+        let mut events = self.game_data.events();
+        events.add_delta_duration(frame_duration);
+        let mut channel = events.channel::<TimerFinished>();
+        finished_timers.into_iter().for_each(|name| channel.publish(TimerFinished::Named(name)));
+    }
+
+    /// Executes a single fixed step
+    pub(crate) fn fixed_update(&mut self) {
+        self.layer_machine.apply_scene_action(SceneAction::FixedUpdate, &mut self.game_data);
+        self.scheduler.execute_stage(Stage::FixedUpdate, &mut self.game_data);
+    }
+
+    /// Clears the frame inputs & events, applies the scene transitions and stops the loop if an exit was requested
+    pub(crate) fn end_frame(&mut self) {
+        self.game_data.inputs().reset_inputs();
+        self.game_data.events().cleanup();
+        self.game_data.commands().end_frame();
+        self.layer_machine.apply_scene_action(SceneAction::EndFrame, &mut self.game_data);
+        let mut window = self.game_data.window();
+        window.reset_close_request();
+        if window.is_exit_requested() {
+            self.running = false;
+        }
+    }
+
+    /// Stops the current scene and the systems, flushes audio & logs, then closes the window
+    pub(crate) fn shutdown(&mut self) {
+        info!("Shutting down the game");
+        self.layer_machine.apply_scene_action(SceneAction::Stop, &mut self.game_data);
+        self.scheduler.shutdown(&mut self.game_data);
+        self.game_data.audio().shutdown();
+        log::logger().flush();
+        if let Some(proxy) = self.event_loop_proxy.take() {
+            let _r = proxy.send_event(());
+        }
+    }
+
     fn update_cursor(&mut self) {
         let mut window = self.game_data.window();
+        let Some(w) = self.window.as_mut() else {
+            window.reset_future_settings();
+            return;
+        };
         if let Some(icon) = window.new_cursor() {
-            let w = self.window.as_mut().expect("A window is mandatory to run this game !");
             w.set_cursor_icon(*icon);
         }
         if let Some(dimensions) = window.new_dimensions() {
-            let w = self.window.as_mut().expect("A window is mandatory to run this game !");
             let _r = w.request_inner_size(Size::Physical(PhysicalSize::new(dimensions.0 * window.dpi() as u32,
                                                                            dimensions.1 * window.dpi() as u32)));
         }
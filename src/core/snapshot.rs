@@ -0,0 +1,463 @@
+//! Serialization of the entities of the world, using the components registered into the [`ComponentRegistry`].
+
+use std::collections::{HashMap, HashSet};
+
+use hecs::{Component, Entity};
+use log::{debug, warn};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::core::components::maths::camera::Camera;
+use crate::core::components::maths::collider::Collider;
+use crate::core::components::maths::hierarchy::{Children, Parent};
+use crate::core::components::maths::transform::Transform;
+use crate::core::components::Persistent;
+use crate::core::scene::transition::TransitionOverlay;
+use crate::core::world::{GameData, World};
+use crate::graphics::components::material::Material;
+use crate::graphics::components::tiles::sprite::Sprite;
+use crate::graphics::components::ui::ui_text::{UiText, UiTextImage};
+use crate::graphics::components::Hide;
+
+/// `SnapshotError` represents the different errors that capturing or restoring a snapshot can return
+#[derive(Debug)]
+pub enum SnapshotError {
+    /// A component or the snapshot can't be (de)serialized. Contains the serialization error message
+    SerializationError(String),
+    /// No component is registered under this name
+    UnregisteredComponent(String),
+}
+
+/// Trait to implement on components referencing other entities, to keep these references valid
+/// when restoring a snapshot, as restored entities get new identifiers
+pub trait MapEntities {
+    fn map_entities(&mut self, entity_map: &EntityMap);
+}
+
+/// Links the entities of a snapshot to the entities created when restoring it
+#[derive(Default, Debug)]
+pub struct EntityMap {
+    entities: HashMap<u64, Entity>,
+}
+
+impl EntityMap {
+    /// Returns the restored entity of the snapshot entity `entity`, or [`Entity::DANGLING`] if it was not in the snapshot
+    pub fn get(&self, entity: Entity) -> Entity {
+        self.entities.get(&entity.to_bits().get()).copied().unwrap_or(Entity::DANGLING)
+    }
+
+    /// Number of restored entities
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+/// A deserialized component, waiting to be added to its entity
+type PendingComponent = Box<dyn FnOnce(&mut GameData, Entity)>;
+
+type CaptureFn = fn(&GameData) -> Result<Vec<(Entity, Value)>, SnapshotError>;
+type EntitiesFn = fn(&GameData) -> Vec<Entity>;
+type DeserializeFn = fn(Value) -> Result<PendingComponent, SnapshotError>;
+type MapEntitiesFn = fn(&mut GameData, Entity, &EntityMap);
+type RemoveFn = fn(&mut GameData, Entity);
+
+/// Functions capturing and restoring a registered component type
+#[derive(Clone)]
+struct RegisteredComponent {
+    name: String,
+    capture: CaptureFn,
+    entities: EntitiesFn,
+    deserialize: DeserializeFn,
+    remove: RemoveFn,
+    map_entities: Option<MapEntitiesFn>,
+    /// Whether the component holds data only valid during the session, like an `AssetRef`, and can't be saved
+    session_only: bool,
+}
+
+impl RegisteredComponent {
+    fn new<T: Component + Serialize + DeserializeOwned>(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            capture: capture_component::<T>,
+            entities: component_entities::<T>,
+            deserialize: deserialize_component::<T>,
+            remove: remove_component::<T>,
+            map_entities: None,
+            session_only: false,
+        }
+    }
+}
+
+/// `ComponentRegistry` is the resource listing the component types saved into a [`WorldSnapshot`] and into the slots of
+/// the [`crate::core::resources::save_manager::SaveManager`], by name.
+/// The engine registers its own serializable components (`Transform`, `Sprite`, `Material`, `Collider`,
+/// `UiText`, `Parent`, `Children`, `Camera`, `Hide` and `Persistent`).
+/// Entities generated by the engine, like the characters of the bitmap `UiText` or the overlay of a scene transition,
+/// are never captured. The function given to `UiText::sync_value` can't be saved: restored texts no longer sync.
+/// `UiText` holds the `AssetRef` of its font, only valid during the session, so it is left out of the save slots.
+#[derive(Clone, Default)]
+pub struct ComponentRegistry {
+    components: Vec<RegisteredComponent>,
+}
+
+impl ComponentRegistry {
+    pub(crate) fn with_engine_components() -> Self {
+        let mut registry = Self::default();
+        registry.register::<Transform>("Transform");
+        registry.register::<Sprite>("Sprite");
+        registry.register::<Material>("Material");
+        registry.register::<Collider>("Collider");
+        registry.register_session_only::<UiText>("UiText");
+        registry.register_with_entities::<Parent>("Parent");
+        registry.register_with_entities::<Children>("Children");
+        registry.register::<Camera>("Camera");
+        registry.register::<Hide>("Hide");
+        registry.register::<Persistent>("Persistent");
+        registry
+    }
+
+    /// Registers the component `T` under `name`, replacing any component registered with the same name
+    pub fn register<T: Component + Serialize + DeserializeOwned>(&mut self, name: &str) {
+        self.add(RegisteredComponent::new::<T>(name));
+    }
+
+    /// Registers the component `T` under `name`, as holding data only valid during the session, like an
+    /// [`crate::core::resources::asset_manager::AssetRef`]. It is captured into the snapshots but not into the save slots.
+    pub fn register_session_only<T: Component + Serialize + DeserializeOwned>(&mut self, name: &str) {
+        self.add(RegisteredComponent { session_only: true, ..RegisteredComponent::new::<T>(name) });
+    }
+
+    /// Registers the component `T`, referencing other entities, under `name`.
+    /// Its references are remapped to the restored entities when restoring a snapshot.
+    pub fn register_with_entities<T: Component + Serialize + DeserializeOwned + MapEntities>(&mut self, name: &str) {
+        self.add(RegisteredComponent { map_entities: Some(map_component_entities::<T>), ..RegisteredComponent::new::<T>(name) });
+    }
+
+    /// Registry of the components that outlive the session, used to save the game
+    pub(crate) fn without_session_only(&self) -> ComponentRegistry {
+        Self { components: self.components.iter().filter(|component| !component.session_only).cloned().collect() }
+    }
+
+    /// Returns whether or not a component is registered under `name`
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.components.iter().any(|component| component.name == name)
+    }
+
+    /// Deserializes `value` into the component registered under `name`, and adds it to `entity`
+    pub(crate) fn insert_component(&self, data: &mut GameData, entity: Entity, name: &str, value: Value) -> Result<(), SnapshotError> {
+        let component = self
+            .components
+            .iter()
+            .find(|component| component.name == name)
+            .ok_or_else(|| SnapshotError::UnregisteredComponent(name.to_string()))?;
+        (component.deserialize)(value)?(data, entity);
+        Ok(())
+    }
+
+    /// Removes the component registered under `name` from `entity`
+    pub(crate) fn remove_component(&self, data: &mut GameData, entity: Entity, name: &str) -> Result<(), SnapshotError> {
+        let component = self
+            .components
+            .iter()
+            .find(|component| component.name == name)
+            .ok_or_else(|| SnapshotError::UnregisteredComponent(name.to_string()))?;
+        (component.remove)(data, entity);
+        Ok(())
+    }
+
+    fn add(&mut self, component: RegisteredComponent) {
+        self.components.retain(|registered| registered.name != component.name);
+        self.components.push(component);
+    }
+
+    /// Captures the registered components of every entity holding at least one of them, except the ones generated
+    /// by the engine
+    pub(crate) fn capture(&self, data: &GameData) -> Result<WorldSnapshot, SnapshotError> {
+        let generated = generated_entities(data);
+        let mut entities: HashMap<Entity, Map<String, Value>> = HashMap::new();
+        for component in self.components.iter() {
+            for (entity, value) in (component.capture)(data)? {
+                if !generated.contains(&entity) {
+                    entities.entry(entity).or_default().insert(component.name.to_string(), value);
+                }
+            }
+        }
+        for (entity, text) in data.query::<&UiText>().iter() {
+            if text.sync_fn.is_some() && entities.contains_key(&entity) {
+                warn!("The sync function of the UiText of {:?} can't be saved, the restored text won't sync", entity);
+            }
+        }
+        let mut entities: Vec<EntitySnapshot> = entities
+            .into_iter()
+            .map(|(entity, components)| EntitySnapshot { id: entity.to_bits().get(), components })
+            .collect();
+        entities.sort_by_key(|entity| entity.id);
+        Ok(WorldSnapshot(entities))
+    }
+
+    /// Removes the entities holding registered components, then creates the entities of `snapshot`.
+    /// The whole snapshot is deserialized first, so the world is left untouched if it is invalid.
+    pub(crate) fn restore(&self, snapshot: &WorldSnapshot, data: &mut GameData) -> Result<EntityMap, SnapshotError> {
+        let mut saved_entities: Vec<(u64, Vec<PendingComponent>)> = Vec::with_capacity(snapshot.0.len());
+        for saved_entity in snapshot.0.iter() {
+            let mut components = Vec::with_capacity(saved_entity.components.len());
+            for (name, value) in saved_entity.components.iter() {
+                match self.components.iter().find(|component| &component.name == name) {
+                    Some(component) => components.push((component.deserialize)(value.clone())?),
+                    None => debug!("Ignoring the component '{}' of the snapshot as it is not registered", name),
+                }
+            }
+            saved_entities.push((saved_entity.id, components));
+        }
+
+        let mut to_remove: HashSet<Entity> =
+            self.components.iter().flat_map(|component| (component.entities)(data)).collect();
+        // Characters of the replaced texts are removed with them, the transition overlay is kept
+        data.query::<&TransitionOverlay>().iter().for_each(|(entity, _)| {
+            to_remove.remove(&entity);
+        });
+        let characters: Vec<Entity> = data
+            .query::<(&UiTextImage, &Parent)>()
+            .iter()
+            .filter(|(_, (_, parent))| to_remove.contains(&parent.0))
+            .map(|(entity, _)| entity)
+            .collect();
+        to_remove.extend(characters);
+        to_remove.into_iter().for_each(|entity| {
+            let _r = data.remove(entity);
+        });
+
+        let mut entity_map = EntityMap::default();
+        let mut restored = Vec::with_capacity(saved_entities.len());
+        for (id, components) in saved_entities {
+            let entity = data.push(());
+            entity_map.entities.insert(id, entity);
+            components.into_iter().for_each(|component| component(data, entity));
+            restored.push(entity);
+        }
+        for map_entities in self.components.iter().filter_map(|component| component.map_entities) {
+            restored.iter().for_each(|entity| map_entities(data, *entity, &entity_map));
+        }
+        Ok(entity_map)
+    }
+}
+
+/// Registered components of an entity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct EntitySnapshot {
+    /// Identifier of the entity when the snapshot was captured
+    id: u64,
+    components: Map<String, Value>,
+}
+
+/// `WorldSnapshot` holds the registered components of the entities of the world, and can be converted to JSON.
+/// ```no_run
+/// use scion::core::snapshot::WorldSnapshot;
+/// use scion::core::world::GameData;
+///
+/// fn reload(data: &mut GameData) {
+///     let json = WorldSnapshot::capture(data).unwrap().to_json().unwrap();
+///     WorldSnapshot::from_json(&json).unwrap().restore(data).unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorldSnapshot(Vec<EntitySnapshot>);
+
+impl WorldSnapshot {
+    /// Captures the components registered into the [`ComponentRegistry`] of every entity holding at least one of them
+    pub fn capture(data: &GameData) -> Result<WorldSnapshot, SnapshotError> {
+        data.component_registry().capture(data)
+    }
+
+    /// Replaces the entities holding registered components by the entities of this snapshot.
+    /// References to other entities of the components registered with [`ComponentRegistry::register_with_entities`]
+    /// are remapped to the restored entities. Returns the map between the snapshot entities and the restored ones.
+    pub fn restore(&self, data: &mut GameData) -> Result<EntityMap, SnapshotError> {
+        let registry = data.component_registry().clone();
+        registry.restore(self, data)
+    }
+
+    /// Number of entities of the snapshot
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, SnapshotError> {
+        serde_json::to_string(self).map_err(serialization_error)
+    }
+
+    pub fn from_json(json: &str) -> Result<WorldSnapshot, SnapshotError> {
+        serde_json::from_str(json).map_err(serialization_error)
+    }
+}
+
+fn capture_component<T: Component + Serialize>(data: &GameData) -> Result<Vec<(Entity, Value)>, SnapshotError> {
+    data.query::<&T>()
+        .iter()
+        .map(|(entity, component)| serde_json::to_value(component).map(|value| (entity, value)).map_err(serialization_error))
+        .collect()
+}
+
+fn component_entities<T: Component>(data: &GameData) -> Vec<Entity> {
+    data.query::<&T>().iter().map(|(entity, _)| entity).collect()
+}
+
+fn deserialize_component<T: Component + DeserializeOwned>(value: Value) -> Result<PendingComponent, SnapshotError> {
+    let component: T = serde_json::from_value(value).map_err(serialization_error)?;
+    Ok(Box::new(move |data: &mut GameData, entity: Entity| {
+        let _r = data.add_components(entity, (component,));
+    }))
+}
+
+/// Entities generated by the engine, that are never captured
+fn generated_entities(data: &GameData) -> HashSet<Entity> {
+    data.query::<&UiTextImage>()
+        .iter()
+        .map(|(entity, _)| entity)
+        .chain(data.query::<&TransitionOverlay>().iter().map(|(entity, _)| entity))
+        .collect()
+}
+
+fn remove_component<T: Component>(data: &mut GameData, entity: Entity) {
+    let _r = data.remove_component::<T>(entity);
+}
+
+fn map_component_entities<T: Component + MapEntities>(data: &mut GameData, entity: Entity, entity_map: &EntityMap) {
+    if let Ok(component) = data.entry_mut::<&mut T>(entity) {
+        component.map_entities(entity_map);
+    }
+}
+
+fn serialization_error(e: serde_json::Error) -> SnapshotError {
+    SnapshotError::SerializationError(e.to_string())
+}
+
+/// Serializes an `Entity` field using its bits, to use with `#[serde(with = "crate::core::snapshot::entity_serde")]`
+pub(crate) mod entity_serde {
+    use hecs::Entity;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(entity: &Entity, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(entity.to_bits().get())
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Entity, D::Error> {
+        let bits = u64::deserialize(deserializer)?;
+        Entity::from_bits(bits).ok_or_else(|| D::Error::custom("Invalid entity"))
+    }
+}
+
+/// Serializes a `Vec<Entity>` field using the entities bits
+pub(crate) mod entities_serde {
+    use hecs::Entity;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(entities: &[Entity], serializer: S) -> Result<S::Ok, S::Error> {
+        entities.iter().map(|entity| entity.to_bits().get()).collect::<Vec<u64>>().serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Entity>, D::Error> {
+        Vec::<u64>::deserialize(deserializer)?
+            .into_iter()
+            .map(|bits| Entity::from_bits(bits).ok_or_else(|| D::Error::custom("Invalid entity")))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::components::maths::collider::{ColliderMask, ColliderType};
+    use crate::graphics::components::color::Color;
+    use crate::graphics::components::ui::ui_image::UiImage;
+
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Target(#[serde(with = "entity_serde")] Entity);
+
+    impl MapEntities for Target {
+        fn map_entities(&mut self, entity_map: &EntityMap) {
+            self.0 = entity_map.get(self.0);
+        }
+    }
+
+    fn game_data() -> GameData {
+        let mut data = GameData::default();
+        let mut registry = ComponentRegistry::with_engine_components();
+        registry.register_with_entities::<Target>("Target");
+        data.insert_resource(registry);
+        data
+    }
+
+    #[test]
+    fn snapshot_roundtrip_test() {
+        let mut data = game_data();
+        let parent = data.push((Transform::from_xyz(1., 2., 3), Material::Diffuse(Color::new_rgb(1, 2, 3)), Persistent));
+        let child = data.push((Transform::from_xy(4., 5.), Parent(parent), Collider::new(ColliderMask::Bullet, vec![ColliderMask::Death], ColliderType::SquareCollider(5))));
+        let _r = data.add_components(parent, (Children(vec![child]),));
+        let other = data.push((Target(child), 1_u8));
+
+        let json = WorldSnapshot::capture(&data).unwrap().to_json().unwrap();
+        let snapshot = WorldSnapshot::from_json(&json).unwrap();
+        assert_eq!(3, snapshot.len());
+        let entity_map = snapshot.restore(&mut data).unwrap();
+
+        assert!(!data.contains(parent) && !data.contains(child) && !data.contains(other));
+        assert_eq!(3, data.entities().len());
+        let new_parent = entity_map.get(parent);
+        let new_child = entity_map.get(child);
+        assert_eq!(vec![new_child], data.entry::<&Children>(new_parent).unwrap().get().unwrap().0);
+        assert_eq!(new_parent, data.entry::<&Parent>(new_child).unwrap().get().unwrap().0);
+        assert_eq!(&Target(new_child), data.query::<&Target>().iter().next().unwrap().1);
+        let translation = *data.entry::<&Transform>(new_parent).unwrap().get().unwrap().translation();
+        assert_eq!((1., 2., 3), (translation.x(), translation.y(), translation.z()));
+        assert!(data.entry::<&Persistent>(new_parent).unwrap().get().is_some());
+        assert_eq!(&ColliderMask::Bullet, data.entry::<&Collider>(new_child).unwrap().get().unwrap().mask());
+        assert_eq!(0, data.query::<&u8>().iter().count());
+    }
+
+    #[test]
+    fn unknown_entities_are_dangling_test() {
+        let mut data = game_data();
+        let outside = Entity::from_bits((1 << 32) | 999).unwrap();
+        data.push((Target(outside),));
+        let snapshot = WorldSnapshot::capture(&data).unwrap();
+        snapshot.restore(&mut data).unwrap();
+        assert_eq!(&Target(Entity::DANGLING), data.query::<&Target>().iter().next().unwrap().1);
+    }
+
+    #[test]
+    fn generated_entities_are_not_captured_test() {
+        let mut data = game_data();
+        let text = data.push((Transform::default(),));
+        data.push((UiTextImage(UiImage::new(1., 1.)), Transform::default(), Parent(text)));
+        let overlay = data.push((TransitionOverlay, Transform::default()));
+
+        let snapshot = WorldSnapshot::capture(&data).unwrap();
+        assert_eq!(1, snapshot.len());
+        snapshot.restore(&mut data).unwrap();
+        assert_eq!(0, data.query::<&UiTextImage>().iter().count());
+        assert!(data.contains(overlay));
+        assert_eq!(2, data.entities().len());
+    }
+
+    #[test]
+    fn invalid_snapshot_is_not_restored_test() {
+        let mut data = game_data();
+        let entity = data.push((Transform::default(),));
+        let json = r#"[{ "id": 1, "components": { "Hide": null } }, { "id": 2, "components": { "Transform": 3 } }]"#;
+
+        let snapshot = WorldSnapshot::from_json(json).unwrap();
+        assert!(matches!(snapshot.restore(&mut data), Err(SnapshotError::SerializationError(_))));
+        assert!(data.contains(entity));
+        assert_eq!(1, data.entities().len());
+    }
+}
@@ -1,12 +1,60 @@
 use std::collections::HashMap;
 
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+/// Name of the `Events` topic where a [`GameStateChange`] is published each time a value of the `GameState` changes
+pub const GAME_STATE_TOPIC: &str = "GameState";
+
 /// `GameState` is a convenience Resource created to keep track of
 /// diverse thing internally. It's also the resource used to create
 /// pausable systems.
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct GameState {
     flags: HashMap<String, bool>,
     text: HashMap<String, String>,
+    values: HashMap<String, Value>,
+    /// Changes waiting to be published into the [`GAME_STATE_TOPIC`] topic
+    #[serde(skip)]
+    changes: Vec<GameStateChange>,
+}
+
+/// `GameStateError` represents the errors returned when storing typed values into the `GameState`
+#[derive(Debug)]
+pub enum GameStateError {
+    /// The value can't be serialized. Contains the serialization error message
+    SerializationError(String),
+}
+
+/// Event published into the [`GAME_STATE_TOPIC`] topic when a value of the `GameState` changes.
+/// Changes made during a frame are published at the beginning of the next one.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GameStateChange {
+    key: String,
+    /// New value, `Null` when the key has been removed
+    value: Value,
+}
+
+impl GameStateChange {
+    /// Key of the changed value
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// New value, if it is of type `T`. None when the key has been removed
+    pub fn value<T: DeserializeOwned>(&self) -> Option<T> {
+        serde_json::from_value(self.value.clone()).ok()
+    }
+
+    /// Returns whether or not the key has been removed
+    pub fn is_removal(&self) -> bool {
+        self.value.is_null()
+    }
+
+    /// New value, formatted to be displayed. Texts are displayed without quotes
+    pub fn display_value(&self) -> String {
+        display(&self.value)
+    }
 }
 
 impl GameState {
@@ -19,7 +67,9 @@ impl GameState {
     }
 
     pub fn set_bool(&mut self, key: &str, val: bool) {
-        self.flags.insert(key.to_string(), val);
+        if self.flags.insert(key.to_string(), val) != Some(val) {
+            self.notify(key, Value::Bool(val));
+        }
     }
 
     pub fn get_text(&self, key: &str) -> Option<String> {
@@ -31,6 +81,168 @@ impl GameState {
     }
 
     pub fn set_text(&mut self, key: &str, val: &str) {
-        self.text.insert(key.to_string(), val.to_string());
+        if self.text.insert(key.to_string(), val.to_string()).as_deref() != Some(val) {
+            self.notify(key, Value::String(val.to_string()));
+        }
+    }
+
+    /// Returns the integer stored for `key`, if any
+    pub fn get_int(&self, key: &str) -> Option<i64> {
+        self.values.get(key).and_then(Value::as_i64)
+    }
+
+    pub fn set_int(&mut self, key: &str, val: i64) {
+        self.set_value(key, Value::from(val));
+    }
+
+    /// Adds `delta` to the integer stored for `key`, starting from 0 if there is none, and returns the new integer
+    pub fn add_int(&mut self, key: &str, delta: i64) -> i64 {
+        let val = self.get_int(key).unwrap_or(0) + delta;
+        self.set_int(key, val);
+        val
+    }
+
+    /// Returns the float stored for `key`, if any. Integers are converted to floats
+    pub fn get_float(&self, key: &str) -> Option<f64> {
+        self.values.get(key).and_then(Value::as_f64)
+    }
+
+    pub fn set_float(&mut self, key: &str, val: f64) {
+        self.set_value(key, Value::from(val));
+    }
+
+    /// Returns the value stored for `key`, if any and if it can be deserialized into `T`
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use scion::core::state::GameState;
+    ///
+    /// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    /// enum Difficulty { Easy, Hard }
+    ///
+    /// let mut state = GameState::default();
+    /// state.set("difficulty", &Difficulty::Hard).unwrap();
+    /// assert_eq!(Some(Difficulty::Hard), state.get::<Difficulty>("difficulty"));
+    /// ```
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.values.get(key).and_then(|val| serde_json::from_value(val.clone()).ok())
+    }
+
+    /// Stores any serializable value for `key`
+    pub fn set<T: Serialize>(&mut self, key: &str, val: &T) -> Result<(), GameStateError> {
+        let val = serde_json::to_value(val).map_err(|e| GameStateError::SerializationError(e.to_string()))?;
+        self.set_value(key, val);
+        Ok(())
+    }
+
+    /// Removes the flag, text and value stored for `key`
+    pub fn remove(&mut self, key: &str) {
+        let flag = self.flags.remove(key);
+        let text = self.text.remove(key);
+        let value = self.values.remove(key);
+        if flag.is_some() || text.is_some() || value.is_some() {
+            self.notify(key, Value::Null);
+        }
+    }
+
+    /// Returns the value stored for `key` formatted to be displayed, looking for a text, then a value, then a flag.
+    pub fn display_value(&self, key: &str) -> Option<String> {
+        self.text
+            .get(key)
+            .cloned()
+            .or_else(|| self.values.get(key).map(display))
+            .or_else(|| self.flags.get(key).map(bool::to_string))
+    }
+
+    /// Replaces every flag, text and value by the ones of `saved`, notifying the keys whose value changed
+    pub(crate) fn restore(&mut self, saved: GameState) {
+        let removed: Vec<String> = self
+            .flags
+            .keys()
+            .chain(self.text.keys())
+            .chain(self.values.keys())
+            .filter(|key| !saved.flags.contains_key(*key) && !saved.text.contains_key(*key) && !saved.values.contains_key(*key))
+            .cloned()
+            .collect();
+        removed.iter().for_each(|key| self.remove(key));
+        self.flags.retain(|key, _| saved.flags.contains_key(key));
+        self.text.retain(|key, _| saved.text.contains_key(key));
+        self.values.retain(|key, _| saved.values.contains_key(key));
+        saved.flags.into_iter().for_each(|(key, val)| self.set_bool(&key, val));
+        saved.text.into_iter().for_each(|(key, val)| self.set_text(&key, &val));
+        saved.values.into_iter().for_each(|(key, val)| self.set_value(&key, val));
+    }
+
+    pub(crate) fn take_changes(&mut self) -> Vec<GameStateChange> {
+        std::mem::take(&mut self.changes)
+    }
+
+    fn set_value(&mut self, key: &str, val: Value) {
+        if self.values.get(key) != Some(&val) {
+            self.values.insert(key.to_string(), val.clone());
+            self.notify(key, val);
+        }
+    }
+
+    fn notify(&mut self, key: &str, value: Value) {
+        self.changes.push(GameStateChange { key: key.to_string(), value });
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.to_string(),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Bonus {
+        Shield,
+        Speed(u8),
+    }
+
+    #[test]
+    fn typed_values_test() {
+        let mut state = GameState::default();
+        state.set_int("lives", 3);
+        assert_eq!(Some(3), state.get_int("lives"));
+        assert_eq!(Some(3.), state.get_float("lives"));
+        assert_eq!(1, state.add_int("lives", -2));
+        state.set_float("speed", 1.5);
+        assert_eq!(Some(1.5), state.get_float("speed"));
+        assert_eq!(None, state.get_int("speed"));
+        state.set("bonus", &Bonus::Speed(2)).unwrap();
+        assert_eq!(Some(Bonus::Speed(2)), state.get::<Bonus>("bonus"));
+        assert_eq!(None, state.get::<Bonus>("lives"));
+        assert_eq!(Some("1".to_string()), state.display_value("lives"));
+        state.remove("lives");
+        assert_eq!(None, state.get_int("lives"));
+    }
+
+    #[test]
+    fn changes_are_only_recorded_for_new_values_test() {
+        let mut state = GameState::default();
+        state.set_int("score", 10);
+        state.set_int("score", 10);
+        state.set_bool("pause", false);
+        state.set_bool("pause", false);
+        state.set_text("name", "scion");
+        state.set("bonus", &Bonus::Shield).unwrap();
+        state.remove("unknown");
+        state.remove("score");
+
+        let changes = state.take_changes();
+        assert_eq!(5, changes.len());
+        assert_eq!("score", changes[0].key());
+        assert_eq!(Some(10), changes[0].value::<i64>());
+        assert_eq!(Some(false), changes[1].value::<bool>());
+        assert_eq!("scion", changes[2].display_value());
+        assert_eq!(Some(Bonus::Shield), changes[3].value::<Bonus>());
+        assert!(changes[4].is_removal());
+        assert!(state.take_changes().is_empty());
     }
 }
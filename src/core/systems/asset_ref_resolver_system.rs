@@ -48,6 +48,7 @@ mod tests {
 
         let mut manager = AssetManager::default();
         let asset_ref = manager.register_material(Material::Diffuse(Color::new(1, 1, 1, 1.)));
+        let other_ref = manager.register_material(Material::Diffuse(Color::new(2, 2, 2, 1.)));
         world.insert_resource(manager);
 
         let e = world.push((1, asset_ref.clone()));
@@ -57,5 +58,15 @@ mod tests {
         asset_ref_resolver_system::<Material, MaterialAssetResolverFn>(&mut world);
 
         assert!(world.entry::<&Material>(e).expect("").get().is_some());
+
+        let _r = world.remove_component::<Material>(e);
+        let _r = world.add_components(e, (other_ref,));
+        asset_ref_resolver_system::<Material, MaterialAssetResolverFn>(&mut world);
+
+        let mut entry = world.entry::<&Material>(e).expect("");
+        match entry.get() {
+            Some(Material::Diffuse(color)) => assert_eq!(2, color.red()),
+            _ => panic!("The material of the replaced asset ref is missing"),
+        }
     }
 }
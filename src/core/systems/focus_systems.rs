@@ -1,7 +1,7 @@
 use hecs::Entity;
 use log::debug;
 
-use crate::graphics::components::{Hide, HidePropagated};
+use crate::graphics::components::{Hide, HidePropagated, SceneHidden};
 use crate::graphics::components::ui::UiFocusable;
 use crate::core::resources::inputs::types::{Input, KeyCode};
 use crate::core::world::{GameData, SubWorld, World};
@@ -74,6 +74,7 @@ fn retrieve_next_focus(current_focus: Option<usize>,
     world.query::<&UiFocusable>()
         .without::<&Hide>()
         .without::<&HidePropagated>()
+        .without::<&SceneHidden>()
         .iter()
         .for_each(|(e, uif)| {
             if min_entity.is_some() {
@@ -109,6 +110,7 @@ fn retrieve_previous_focus(current_focus: Option<usize>,
     world.query::<&UiFocusable>()
         .without::<&Hide>()
         .without::<&HidePropagated>()
+        .without::<&SceneHidden>()
         .iter()
         .for_each(|(e, uif)| {
             if max_entity.is_some() {
@@ -0,0 +1,44 @@
+use crate::core::resources::events::Events;
+use crate::core::state::{GameState, GAME_STATE_TOPIC};
+use crate::core::world::GameData;
+
+/// System responsible of publishing the changes of the `GameState` values into the [`GAME_STATE_TOPIC`] topic
+pub(crate) fn game_state_events_system(data: &mut GameData) {
+    let changes = match data.get_resource_mut::<GameState>() {
+        Some(mut game_state) => game_state.take_changes(),
+        None => return,
+    };
+    if let Some(mut events) = data.get_resource_mut::<Events>() {
+        changes.into_iter().for_each(|change| {
+            let _r = events.publish(GAME_STATE_TOPIC, change);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::resources::events::topic::TopicConfiguration;
+    use crate::core::resources::events::PollConfiguration;
+    use crate::core::state::GameStateChange;
+
+    use super::*;
+
+    #[test]
+    fn game_state_changes_are_published_test() {
+        let mut data = GameData::default();
+        let mut events = Events::default();
+        events.create_topic(GAME_STATE_TOPIC, TopicConfiguration::default()).unwrap();
+        let subscription = events.subscribe(GAME_STATE_TOPIC, PollConfiguration::default()).unwrap();
+        data.insert_resource(events);
+        data.insert_resource(GameState::default());
+
+        data.game_state_mut().set_int("score", 100);
+        game_state_events_system(&mut data);
+        game_state_events_system(&mut data);
+
+        let changes = data.events().poll::<GameStateChange>(&subscription).unwrap();
+        assert_eq!(1, changes.len());
+        assert_eq!("score", changes[0].key());
+        assert_eq!(Some(100), changes[0].value::<i64>());
+    }
+}
@@ -54,6 +54,11 @@ mod tests {
         missing_ui_component_system::<UiImage>(&mut world);
 
         assert!(world.entry::<&UiComponent>(e).expect("").get().is_some());
+
+        let _r = world.remove_component::<UiComponent>(e);
+        missing_ui_component_system::<UiImage>(&mut world);
+
+        assert!(world.entry::<&UiComponent>(e).expect("").get().is_some());
     }
 
     #[test]
@@ -4,6 +4,7 @@ use crate::graphics::components::shapes::line::Line;
 use crate::graphics::components::shapes::polygon::Polygon;
 use crate::graphics::components::shapes::rectangle::Rectangle;
 use crate::graphics::components::tiles::sprite::Sprite;
+use crate::graphics::components::tween::{ColorAlpha, TransformAngle, TransformPosition, TransformScale};
 use crate::graphics::components::ui::ui_button::UiButton;
 use crate::graphics::components::ui::ui_image::UiImage;
 use crate::graphics::components::ui::ui_input::UiInput;
@@ -11,47 +12,66 @@ use crate::graphics::components::ui::ui_text::{UiText, UiTextImage};
 use crate::core::package::Package;
 use crate::core::resources::asset_manager::AssetManager;
 use crate::core::resources::audio::Audio;
+use crate::core::resources::commands::Commands;
 use crate::core::resources::events::Events;
 use crate::core::resources::events::topic::TopicConfiguration;
 use crate::core::resources::focus_manager::FocusManager;
 use crate::core::resources::font_atlas::FontAtlas;
 use crate::core::resources::global_storage::GlobalStorage;
 use crate::core::resources::inputs::inputs_controller::InputsController;
+use crate::core::resources::random::Random;
 use crate::core::resources::time::{Time, Timers, TimerType};
 use crate::core::scene::SceneController;
-use crate::core::state::GameState;
+use crate::core::scene::loading::LoadingProgress;
+use crate::core::snapshot::ComponentRegistry;
+use crate::core::state::{GameState, GAME_STATE_TOPIC};
 use crate::core::systems::animations_system::animation_executer_system;
 use crate::core::systems::asset_ref_resolver_system::asset_ref_resolver_system;
 use crate::core::systems::asset_ref_resolver_system::MaterialAssetResolverFn;
 use crate::core::systems::collider_systems::{collider_cleaner_system, collider_pivot_propagation_system, compute_collisions_system, debug_colliders_system};
 use crate::core::systems::default_camera_system::default_camera_system;
 use crate::core::systems::focus_systems::focus_switcher_system;
+use crate::core::systems::game_state_system::game_state_events_system;
 use crate::core::systems::hide_propagation_system::{
     hide_propagated_deletion_system, hide_propagation_system,
 };
 use crate::core::systems::hierarchy_system::children_manager_system;
 use crate::core::systems::missing_ui_component_system::{missing_focus_component_system, missing_ui_component_system};
 use crate::core::systems::parent_transform_system::{dirty_child_system, dirty_transform_system};
+use crate::core::systems::prefab_system::prefab_hot_reload_system;
+use crate::core::systems::timer_system::entity_timers_system;
+use crate::core::systems::tween_system::tween_system;
 use crate::core::systems::ui_button_systems::{compute_hover, set_childs_on_buttons};
 use crate::core::systems::ui_input_systems::{register_keyboard_inputs_on_ui_input, set_childs_on_inputs, synchronize_input_and_text};
-use crate::core::systems::ui_text_system::{sync_text_value_system, ui_text_bitmap_update_system};
+use crate::core::systems::ui_text_system::{sync_text_value_system, ui_text_bitmap_update_system, UiTextStateSubscription};
 use crate::core::world::GameData;
 use crate::ScionBuilder;
+use crate::core::scheduler::{Stage, System, SystemConfig};
 
 pub(crate) mod animations_system;
 pub(crate) mod asset_ref_resolver_system;
 pub(crate) mod collider_systems;
 pub(crate) mod default_camera_system;
+pub(crate) mod game_state_system;
 pub(crate) mod hide_propagation_system;
 pub(crate) mod hierarchy_system;
 pub(crate) mod missing_ui_component_system;
 pub(crate) mod parent_transform_system;
+pub(crate) mod prefab_system;
+pub(crate) mod timer_system;
+pub(crate) mod tween_system;
 pub(crate) mod ui_text_system;
 pub(crate) mod ui_input_systems;
 pub(crate) mod focus_systems;
 pub(crate) mod ui_button_systems;
 
+/// Name of the timer cycling when the modified asset files should be reloaded, only created with the `hot-reload` feature
+pub(crate) const HOT_RELOAD_TIMER: &str = "hot-reload-timer";
 
+/// Internal systems are all executed in the `PreUpdate` stage, labelled with their function name.
+fn internal_system<S: System>(system: S, label: &str) -> SystemConfig {
+    SystemConfig::new(system).in_stage(Stage::PreUpdate).label(label)
+}
 
 pub(crate) struct InternalPackage;
 impl Package for InternalPackage {
@@ -60,11 +80,17 @@ impl Package for InternalPackage {
         events
             .create_topic("Inputs", TopicConfiguration::default())
             .expect("Error while creating topic for inputs event");
+        events
+            .create_topic(GAME_STATE_TOPIC, TopicConfiguration::default())
+            .expect("Error while creating topic for game state changes");
+        let ui_text_subscription = events
+            .subscribe(GAME_STATE_TOPIC, Default::default())
+            .expect("Error while subscribing to game state changes");
 
         let mut timers = Timers::default();
 
         if cfg!(feature = "hot-reload") {
-            let _res = timers.add_timer("hot-reload-timer", TimerType::Cyclic, 5.);
+            timers.add_internal_timer(HOT_RELOAD_TIMER, TimerType::Cyclic, 5.);
         }
 
         data.insert_resource(Time::default());
@@ -74,44 +100,56 @@ impl Package for InternalPackage {
         data.insert_resource(AssetManager::default());
         data.insert_resource(InputsController::default());
         data.insert_resource(GameState::default());
+        data.insert_resource(UiTextStateSubscription(ui_text_subscription));
         data.insert_resource(SceneController::default());
+        data.insert_resource(LoadingProgress::default());
+        data.insert_resource(ComponentRegistry::with_engine_components());
+        data.insert_resource(Random::default());
         data.insert_resource(Audio::default());
         data.insert_resource(FontAtlas::default());
         data.insert_resource(GlobalStorage::default());
+        data.insert_resource(Commands::default());
     }
 
     fn load(&self, builder: ScionBuilder) -> ScionBuilder {
 
         builder
-            .with_system(collider_cleaner_system)
-            .with_system(default_camera_system)
-            .with_system(sync_text_value_system)
-            .with_system(ui_text_bitmap_update_system)
-            .with_system(children_manager_system)
-            .with_system(hide_propagated_deletion_system)
-            .with_system(hide_propagation_system)
-            .with_system(collider_pivot_propagation_system::<Sprite>)
-            .with_system(collider_pivot_propagation_system::<Rectangle>)
-            .with_system(collider_pivot_propagation_system::<Square>)
-            .with_system(collider_pivot_propagation_system::<Triangle>)
-            .with_system(collider_pivot_propagation_system::<Polygon>)
-            .with_system(collider_pivot_propagation_system::<Line>)
-            .with_system(debug_colliders_system)
-            .with_system(missing_ui_component_system::<UiImage>)
-            .with_system(missing_ui_component_system::<UiTextImage>)
-            .with_system(missing_ui_component_system::<UiText>)
-            .with_system(missing_ui_component_system::<UiButton>)
-            .with_system(missing_focus_component_system::<UiInput>)
-            .with_system(asset_ref_resolver_system::<Material, MaterialAssetResolverFn>)
-            .with_system(animation_executer_system)
-            .with_system(dirty_child_system)
-            .with_system(dirty_transform_system)
-            .with_system(compute_collisions_system)
-            .with_system(set_childs_on_inputs)
-            .with_system(set_childs_on_buttons)
-            .with_system(compute_hover)
-            .with_system(focus_switcher_system)
-            .with_system(register_keyboard_inputs_on_ui_input)
-            .with_system(synchronize_input_and_text)
+            .with_system_config(internal_system(collider_cleaner_system, "collider_cleaner_system"))
+            .with_system_config(internal_system(default_camera_system, "default_camera_system"))
+            .with_system_config(internal_system(game_state_events_system, "game_state_events_system"))
+            .with_system_config(internal_system(sync_text_value_system, "sync_text_value_system").after("game_state_events_system"))
+            .with_system_config(internal_system(ui_text_bitmap_update_system, "ui_text_bitmap_update_system"))
+            .with_system_config(internal_system(entity_timers_system, "entity_timers_system"))
+            .with_system_config(internal_system(prefab_hot_reload_system, "prefab_hot_reload_system"))
+            .with_system_config(internal_system(children_manager_system, "children_manager_system"))
+            .with_system_config(internal_system(hide_propagated_deletion_system, "hide_propagated_deletion_system"))
+            .with_system_config(internal_system(hide_propagation_system, "hide_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Sprite>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Rectangle>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Square>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Triangle>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Polygon>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(collider_pivot_propagation_system::<Line>, "collider_pivot_propagation_system"))
+            .with_system_config(internal_system(debug_colliders_system, "debug_colliders_system"))
+            .with_system_config(internal_system(missing_ui_component_system::<UiImage>, "missing_ui_component_system"))
+            .with_system_config(internal_system(missing_ui_component_system::<UiTextImage>, "missing_ui_component_system"))
+            .with_system_config(internal_system(missing_ui_component_system::<UiText>, "missing_ui_component_system"))
+            .with_system_config(internal_system(missing_ui_component_system::<UiButton>, "missing_ui_component_system"))
+            .with_system_config(internal_system(missing_focus_component_system::<UiInput>, "missing_focus_component_system"))
+            .with_system_config(internal_system(asset_ref_resolver_system::<Material, MaterialAssetResolverFn>, "asset_ref_resolver_system"))
+            .with_system_config(internal_system(tween_system::<TransformPosition>, "tween_system"))
+            .with_system_config(internal_system(tween_system::<TransformScale>, "tween_system"))
+            .with_system_config(internal_system(tween_system::<TransformAngle>, "tween_system"))
+            .with_system_config(internal_system(tween_system::<ColorAlpha>, "tween_system"))
+            .with_system_config(internal_system(animation_executer_system, "animation_executer_system"))
+            .with_system_config(internal_system(dirty_child_system, "dirty_child_system"))
+            .with_system_config(internal_system(dirty_transform_system, "dirty_transform_system"))
+            .with_system_config(internal_system(compute_collisions_system, "compute_collisions_system"))
+            .with_system_config(internal_system(set_childs_on_inputs, "set_childs_on_inputs"))
+            .with_system_config(internal_system(set_childs_on_buttons, "set_childs_on_buttons"))
+            .with_system_config(internal_system(compute_hover, "compute_hover"))
+            .with_system_config(internal_system(focus_switcher_system, "focus_switcher_system"))
+            .with_system_config(internal_system(register_keyboard_inputs_on_ui_input, "register_keyboard_inputs_on_ui_input"))
+            .with_system_config(internal_system(synchronize_input_and_text, "synchronize_input_and_text"))
     }
 }
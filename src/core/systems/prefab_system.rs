@@ -0,0 +1,19 @@
+use crate::core::prefab::update_prefab_instances;
+use crate::core::systems::HOT_RELOAD_TIMER;
+use crate::core::world::GameData;
+
+/// System responsible to reload the modified prefab files and to update the entities spawned from them,
+/// when the `hot-reload` feature is activated
+pub(crate) fn prefab_hot_reload_system(data: &mut GameData) {
+    let should_reload = match data.timers().get_timer(HOT_RELOAD_TIMER) {
+        Ok(timer) => timer.just_finished(),
+        Err(_) => false,
+    };
+    if !should_reload {
+        return;
+    }
+    let reloaded = data.assets_mut().reload_modified_prefabs();
+    if !reloaded.is_empty() {
+        update_prefab_instances(data, &reloaded);
+    }
+}
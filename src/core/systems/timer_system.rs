@@ -0,0 +1,65 @@
+use crate::core::resources::time::{Finished, Time, Timer, TimerFinished};
+use crate::core::world::{GameData, World};
+
+/// System responsible to tick the `Timer` components, marking the entities whose timer finished during this frame
+/// with `Finished` and publishing a `TimerFinished` event for each of them
+pub(crate) fn entity_timers_system(data: &mut GameData) {
+    let delta = data
+        .get_resource::<Time>()
+        .expect("Time is an internal resource and can't be missing")
+        .delta_duration()
+        .as_secs_f32();
+
+    let mut finished = Vec::new();
+    for (e, timer) in data.query_mut::<&mut Timer>() {
+        if timer.add_delta_duration(delta) {
+            finished.push(e);
+        }
+    }
+    let outdated: Vec<_> = data.query::<&Finished>().iter().map(|(e, _)| e).filter(|e| !finished.contains(e)).collect();
+
+    outdated.iter().for_each(|e| {
+        let _r = data.remove_component::<Finished>(*e);
+    });
+    finished.iter().for_each(|e| {
+        let _r = data.add_components(*e, (Finished,));
+    });
+    let mut events = data.events();
+    let mut channel = events.channel::<TimerFinished>();
+    finished.drain(0..).for_each(|e| channel.publish(TimerFinished::Entity(e)));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::core::resources::events::{Events, PollConfiguration};
+    use crate::core::resources::time::TimerType;
+
+    use super::*;
+
+    #[test]
+    fn entity_timers_system_test() {
+        let mut world = GameData::default();
+        world.insert_resource(Time::default());
+        world.insert_resource(Events::default());
+        let subscriber = world.events().channel::<TimerFinished>().subscribe(PollConfiguration::default());
+        let cooldown = world.push((Timer::new(0.5, TimerType::Manual),));
+        let cycling = world.push((Timer::new(1., TimerType::Cyclic),));
+
+        let tick = |world: &mut GameData| {
+            world.get_resource_mut::<Time>().unwrap().advance(Duration::from_millis(600));
+            entity_timers_system(world);
+            (world.entry::<&Finished>(cooldown).unwrap().get().is_some(),
+             world.entry::<&Finished>(cycling).unwrap().get().is_some())
+        };
+
+        assert_eq!((true, false), tick(&mut world));
+        assert_eq!((false, true), tick(&mut world));
+        assert_eq!((false, false), tick(&mut world));
+        assert_eq!(
+            vec![TimerFinished::Entity(cooldown), TimerFinished::Entity(cycling)],
+            world.events().channel::<TimerFinished>().poll(&subscriber).unwrap().into_iter().collect::<Vec<_>>()
+        );
+    }
+}
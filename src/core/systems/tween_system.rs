@@ -0,0 +1,14 @@
+use crate::core::resources::time::Time;
+use crate::core::world::{GameData, World};
+use crate::graphics::components::tween::{Lens, Tween};
+
+/// System responsible to advance the tweens using the lens `L`, and to apply their values to the targeted components
+pub(crate) fn tween_system<L: Lens>(data: &mut GameData) {
+    let delta = data
+        .get_resource::<Time>()
+        .expect("Time is an internal resource and can't be missing")
+        .delta_duration();
+    for (_, (tween, component)) in data.query_mut::<(&mut Tween<L>, &mut L::Component)>() {
+        tween.advance(delta, component);
+    }
+}
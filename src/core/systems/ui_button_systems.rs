@@ -1,7 +1,7 @@
 use hecs::Entity;
 use winit::window::CursorIcon;
 
-use crate::graphics::components::{Hide, HidePropagated};
+use crate::graphics::components::{Hide, HidePropagated, SceneHidden};
 use crate::graphics::components::color::Color;
 use crate::graphics::components::material::Material;
 use crate::core::components::maths::hierarchy::{Children, Parent};
@@ -52,7 +52,8 @@ pub(crate) fn compute_hover(data: &mut GameData) {
     for (_, (ui_button, transform, children))
     in world.query_mut::<(&mut UiButton, &Transform, &mut Children)>()
         .without::<&Hide>()
-        .without::<&HidePropagated>() {
+        .without::<&HidePropagated>()
+        .without::<&SceneHidden>() {
         if transform.global_translation.x as f64 <= mx
             && (transform.global_translation.x + ui_button.width() as f32) as f64 >= mx
             && transform.global_translation.y as f64 <= my
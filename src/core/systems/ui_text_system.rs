@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use atomic_refcell::AtomicRefMut;
 use hecs::Entity;
@@ -17,18 +17,51 @@ use crate::graphics::components::{
 };
 use crate::graphics::components::color::Color;
 use crate::graphics::components::material::Material;
+use crate::core::resources::events::{Events, SubscriberId};
 use crate::core::resources::font_atlas::FontAtlas;
+use crate::core::state::{GameState, GameStateChange};
 use crate::core::world::{GameData, World};
 
+/// Subscription of the text synchronization to the game state changes
+pub(crate) struct UiTextStateSubscription(pub(crate) SubscriberId);
+
 pub(crate) fn sync_text_value_system(data: &mut GameData) {
+    let changed_values = poll_game_state_changes(data);
     let (world, resources) = data.split();
     for(_e, ui_text) in world.query_mut::<&mut UiText>(){
         if let Some(function) = ui_text.sync_fn {
             ui_text.set_text(function(resources));
         }
+        if let Some(key) = ui_text.sync_key.clone() {
+            if !ui_text.key_synced {
+                if let Some(value) = resources.get_resource::<GameState>().and_then(|state| state.display_value(&key)) {
+                    ui_text.set_text(value);
+                }
+                ui_text.key_synced = true;
+            } else if let Some(value) = changed_values.get(&key) {
+                ui_text.set_text(value.to_string());
+            }
+        }
     }
 }
 
+/// Reads the game state changes since the last frame, returning the last value displayed for each changed key
+fn poll_game_state_changes(data: &mut GameData) -> HashMap<String, String> {
+    let mut changed_values = HashMap::new();
+    let Some(subscription) = data.get_resource::<UiTextStateSubscription>().map(|s| s.0) else { return changed_values; };
+    let Some(mut events) = data.get_resource_mut::<Events>() else { return changed_values; };
+    while let Ok(changes) = events.poll::<GameStateChange>(&subscription) {
+        if changes.is_empty() {
+            break;
+        }
+        changes.into_iter().for_each(|change| {
+            let value = if change.is_removal() { String::new() } else { change.display_value() };
+            changed_values.insert(change.key().to_string(), value);
+        });
+    }
+    changed_values
+}
+
 pub(crate) fn ui_text_bitmap_update_system(data: &mut GameData) {
     let mut parent_to_remove: HashSet<Entity> = HashSet::new();
     let mut to_add: Vec<(UiTextImage, Material, UiComponent, Transform, Parent)> = Vec::new();
@@ -126,9 +159,12 @@ pub(crate) fn ui_text_bitmap_update_system(data: &mut GameData) {
 fn add_font_to_atlas_if_missing(size: usize, color: &Color, font_path: &str,  font_atlas: &mut AtomicRefMut<FontAtlas>) {
     if font_atlas.get_texture(font_path, size, color).is_none() {
         debug!("Adding font to atlas: [path: {}; size:{}; color:{:?}", font_path, size, color);
-        let res = crate::core::resources::font_atlas::generate_bitmap(Font::TrueType { font_path: font_path.to_string() },
-                                                                      size,
-                                                                      color);
+        let res = match font_atlas.get_font_file(font_path) {
+            Some(font_file) => crate::core::resources::font_atlas::generate_bitmap_from_bytes(font_file.clone(), size, color),
+            None => crate::core::resources::font_atlas::generate_bitmap(Font::TrueType { font_path: font_path.to_string() },
+                                                                        size,
+                                                                        color),
+        };
         if let Ok(texture) = res {
             font_atlas.add_texture(font_path.to_string(), size, color, texture);
         }
@@ -270,4 +306,37 @@ fn update_bitmap(texture_path: String,
             let txt = world.query::<&UiText>().iter().next().unwrap().1.text().to_string();
             assert_eq!("5".to_string(), txt);
         }
+
+        #[test]
+        fn ui_text_synchronized_with_game_state() {
+            use crate::core::resources::events::topic::TopicConfiguration;
+            use crate::core::state::GAME_STATE_TOPIC;
+            use crate::core::systems::game_state_system::game_state_events_system;
+
+            let mut world = GameData::default();
+            let mut events = Events::default();
+            events.create_topic(GAME_STATE_TOPIC, TopicConfiguration::default()).unwrap();
+            let subscription = events.subscribe(GAME_STATE_TOPIC, Default::default()).unwrap();
+            world.insert_resource(events);
+            world.insert_resource(UiTextStateSubscription(subscription));
+            world.insert_resource(GameState::default());
+            world.game_state_mut().set_int("score", 5);
+
+            let mut manager = AssetManager::default();
+            let text_synced = get_test_ui_text(&mut manager).sync_state("score");
+            let _entity = world.push((text_synced, Transform::default()));
+            world.insert_resource(manager);
+            let text = |world: &GameData| world.query::<&UiText>().iter().next().unwrap().1.text().to_string();
+
+            game_state_events_system(&mut world);
+            sync_text_value_system(&mut world);
+            assert_eq!("5", text(&world));
+
+            world.game_state_mut().add_int("score", 10);
+            sync_text_value_system(&mut world);
+            assert_eq!("5", text(&world));
+            game_state_events_system(&mut world);
+            sync_text_value_system(&mut world);
+            assert_eq!("15", text(&world));
+        }
     }
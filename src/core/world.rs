@@ -10,16 +10,22 @@ use hecs::{
     QueryMut, QueryOne, QueryOneError,
 };
 
+use crate::core::change_detection::LifecycleEvent;
 use crate::core::components::maths::camera::{Camera, DefaultCamera};
+use crate::core::components::maths::hierarchy::Parent;
 use crate::core::resources::asset_manager::AssetManager;
 use crate::core::resources::audio::Audio;
+use crate::core::resources::commands::Commands;
 use crate::core::resources::events::Events;
 use crate::core::resources::focus_manager::FocusManager;
 use crate::core::resources::font_atlas::FontAtlas;
 use crate::core::resources::inputs::inputs_controller::InputsController;
+use crate::core::resources::random::Random;
+use crate::core::resources::save_manager::SaveManager;
+use crate::core::snapshot::ComponentRegistry;
 use crate::core::resources::time::Timers;
 use crate::core::resources::window::Window;
-use crate::core::scene::SceneController;
+use crate::core::scene::{SceneController, SceneId, SceneOwned};
 use crate::core::state::GameState;
 
 pub trait World {
@@ -125,6 +131,12 @@ impl GameData {
             .expect("The engine is missing the mandatory events resource")
     }
 
+    /// retrieves the commands resource from the resources
+    pub fn commands(&self) -> AtomicRefMut<Commands> {
+        self.get_resource_mut::<Commands>()
+            .expect("The engine is missing the mandatory commands resource")
+    }
+
     /// retrieves the audio player from the resources
     pub fn audio(&self) -> AtomicRefMut<Audio> {
         self.get_resource_mut::<Audio>()
@@ -143,6 +155,24 @@ impl GameData {
             .expect("The engine is missing the mandatory scene controller resource")
     }
 
+    /// retrieves the save manager from the resources.
+    pub fn save_manager(&self) -> AtomicRefMut<SaveManager> {
+        self.get_resource_mut::<SaveManager>()
+            .expect("The engine is missing the mandatory save manager resource")
+    }
+
+    /// retrieves the component registry from the resources.
+    pub fn component_registry(&self) -> AtomicRefMut<ComponentRegistry> {
+        self.get_resource_mut::<ComponentRegistry>()
+            .expect("The engine is missing the mandatory component registry resource")
+    }
+
+    /// retrieves the random resource from the resources.
+    pub fn random(&self) -> AtomicRefMut<Random> {
+        self.get_resource_mut::<Random>()
+            .expect("The engine is missing the mandatory random resource")
+    }
+
     /// retrieves the font_atlas from the resources.
     pub(crate) fn font_atlas(&self) -> AtomicRefMut<FontAtlas> {
         self.get_resource_mut::<FontAtlas>()
@@ -156,6 +186,18 @@ impl GameData {
             .expect("The engine is missing the mandatory focus manager resource")
     }
 
+    /// Publishes the lifecycle events recorded since the previous call into the `Events`
+    pub(crate) fn flush_lifecycle_events(&mut self) {
+        let Some(recorded) = self.subworld.lifecycle_events.as_mut().filter(|events| !events.is_empty()) else {
+            return;
+        };
+        let recorded = std::mem::take(recorded);
+        if let Some(mut events) = self.get_resource_mut::<Events>() {
+            let mut channel = events.channel::<LifecycleEvent>();
+            recorded.into_iter().for_each(|event| channel.publish(event));
+        }
+    }
+
     pub(crate) fn has_camera(&self)-> bool{
         self.subworld.query::<&Camera>().iter().count() > 0
     }
@@ -163,23 +205,19 @@ impl GameData {
 
 impl World for GameData {
     fn entities(&self) -> HashSet<Entity> {
-        self.subworld
-            .internal_world
-            .iter()
-            .map(|entity_ref| entity_ref.entity())
-            .collect::<HashSet<_>>()
+        self.subworld.entities()
     }
 
     fn clear(&mut self) {
-        self.subworld.internal_world.clear();
+        self.subworld.clear();
     }
 
     fn push(&mut self, components: impl DynamicBundle) -> Entity {
-        self.subworld.internal_world.spawn(components)
+        self.subworld.push(components)
     }
 
     fn remove(&mut self, entity: Entity) -> Result<(), NoSuchEntity> {
-        self.subworld.internal_world.despawn(entity)
+        self.subworld.remove(entity)
     }
 
     fn add_components(
@@ -187,11 +225,11 @@ impl World for GameData {
         entity: Entity,
         components: impl DynamicBundle,
     ) -> Result<(), NoSuchEntity> {
-        self.subworld.internal_world.insert(entity, components)
+        self.subworld.add_components(entity, components)
     }
 
     fn remove_component<T: Component>(&mut self, entity: Entity) -> Result<T, ComponentError> {
-        self.subworld.internal_world.remove_one::<T>(entity)
+        self.subworld.remove_component::<T>(entity)
     }
 
     fn query<Q: Query>(&self) -> QueryBorrow<'_, Q> {
@@ -222,6 +260,47 @@ impl World for GameData {
 #[derive(Default)]
 pub struct SubWorld {
     internal_world: hecs::World,
+    /// Scene owning the entities created now
+    pub(crate) scene_owner: Option<SceneId>,
+    /// Lifecycle events not yet published, None when lifecycle events are disabled
+    lifecycle_events: Option<Vec<LifecycleEvent>>,
+}
+
+impl SubWorld {
+    /// Starts recording the lifecycle events of the entities, see [`LifecycleEvent`]
+    pub(crate) fn enable_lifecycle_events(&mut self) {
+        if self.lifecycle_events.is_none() {
+            self.lifecycle_events = Some(Vec::new());
+        }
+    }
+
+    fn record_lifecycle_events(&mut self, recorded: Vec<LifecycleEvent>) {
+        if let Some(events) = self.lifecycle_events.as_mut() {
+            events.extend(recorded);
+        }
+    }
+
+    /// Scene owning the parent of `entity`, inherited by `entity` so that it stops with its parent whatever the running
+    /// scene is. Returns None if `entity` has no parent
+    fn parent_owner(&self, entity: Entity) -> Option<Option<SceneId>> {
+        let parent = self.internal_world.get::<&Parent>(entity).ok()?.0;
+        Some(self.internal_world.get::<&SceneOwned>(parent).ok().map(|owned| owned.0))
+    }
+
+    fn component_types(&self, entity: Entity) -> Vec<TypeId> {
+        self.internal_world.entity(entity).map(|e| e.component_types().collect()).unwrap_or_default()
+    }
+
+    /// Events recorded when despawning `entity`
+    fn despawn_events(&self, entity: Entity) -> Vec<LifecycleEvent> {
+        let mut events: Vec<LifecycleEvent> = self
+            .component_types(entity)
+            .into_iter()
+            .map(|type_id| LifecycleEvent::ComponentRemoved(entity, type_id))
+            .collect();
+        events.push(LifecycleEvent::EntityDespawned(entity));
+        events
+    }
 }
 
 #[derive(Default)]
@@ -235,15 +314,35 @@ impl World for SubWorld {
     }
 
     fn clear(&mut self) {
+        if self.lifecycle_events.is_some() {
+            let mut events = Vec::new();
+            self.internal_world.iter().for_each(|entity_ref| events.extend(self.despawn_events(entity_ref.entity())));
+            self.record_lifecycle_events(events);
+        }
         self.internal_world.clear();
     }
 
     fn push(&mut self, components: impl DynamicBundle) -> Entity {
-        self.internal_world.spawn(components)
+        let entity = self.internal_world.spawn(components);
+        let owner = self.parent_owner(entity).unwrap_or(self.scene_owner);
+        if let Some(owner) = owner {
+            let _r = self.internal_world.insert_one(entity, SceneOwned(owner));
+        }
+        if self.lifecycle_events.is_some() {
+            let mut events = vec![LifecycleEvent::EntitySpawned(entity)];
+            events.extend(self.component_types(entity).into_iter().map(|type_id| LifecycleEvent::ComponentAdded(entity, type_id)));
+            self.record_lifecycle_events(events);
+        }
+        entity
     }
 
     fn remove(&mut self, entity: Entity) -> Result<(), NoSuchEntity> {
-        self.internal_world.despawn(entity)
+        let events = self.lifecycle_events.as_ref().map(|_| self.despawn_events(entity));
+        self.internal_world.despawn(entity)?;
+        if let Some(events) = events {
+            self.record_lifecycle_events(events);
+        }
+        Ok(())
     }
 
     fn add_components(
@@ -251,11 +350,36 @@ impl World for SubWorld {
         entity: Entity,
         components: impl DynamicBundle,
     ) -> Result<(), NoSuchEntity> {
-        self.internal_world.insert(entity, components)
+        let previous_types = self.lifecycle_events.as_ref().map(|_| self.component_types(entity));
+        self.internal_world.insert(entity, components)?;
+        if let Some(owner) = self.parent_owner(entity) {
+            match owner {
+                Some(owner) => {
+                    let _r = self.internal_world.insert_one(entity, SceneOwned(owner));
+                }
+                None => {
+                    let _r = self.internal_world.remove_one::<SceneOwned>(entity);
+                }
+            }
+        }
+        if let Some(previous_types) = previous_types {
+            let added: Vec<LifecycleEvent> = self
+                .component_types(entity)
+                .into_iter()
+                .filter(|type_id| !previous_types.contains(type_id))
+                .map(|type_id| LifecycleEvent::ComponentAdded(entity, type_id))
+                .collect();
+            self.record_lifecycle_events(added);
+        }
+        Ok(())
     }
 
     fn remove_component<T: Component>(&mut self, entity: Entity) -> Result<T, ComponentError> {
-        self.internal_world.remove_one::<T>(entity)
+        let component = self.internal_world.remove_one::<T>(entity)?;
+        if self.lifecycle_events.is_some() {
+            self.record_lifecycle_events(vec![LifecycleEvent::ComponentRemoved(entity, TypeId::of::<T>())]);
+        }
+        Ok(component)
     }
 
     fn query<Q: Query>(&self) -> QueryBorrow<'_, Q> {
@@ -355,6 +479,12 @@ impl Resources {
             .expect("The engine is missing the mandatory events resource")
     }
 
+    /// retrieves the commands resource from the resources
+    pub fn commands(&self) -> AtomicRefMut<Commands> {
+        self.get_resource_mut::<Commands>()
+            .expect("The engine is missing the mandatory commands resource")
+    }
+
     /// retrieves the audio player from the resources
     pub fn audio(&self) -> AtomicRefMut<Audio> {
         self.get_resource_mut::<Audio>()
@@ -373,6 +503,24 @@ impl Resources {
             .expect("The engine is missing the mandatory scene controller resource")
     }
 
+    /// retrieves the save manager from the resources.
+    pub fn save_manager(&self) -> AtomicRefMut<SaveManager> {
+        self.get_resource_mut::<SaveManager>()
+            .expect("The engine is missing the mandatory save manager resource")
+    }
+
+    /// retrieves the component registry from the resources.
+    pub fn component_registry(&self) -> AtomicRefMut<ComponentRegistry> {
+        self.get_resource_mut::<ComponentRegistry>()
+            .expect("The engine is missing the mandatory component registry resource")
+    }
+
+    /// retrieves the random resource from the resources.
+    pub fn random(&self) -> AtomicRefMut<Random> {
+        self.get_resource_mut::<Random>()
+            .expect("The engine is missing the mandatory random resource")
+    }
+
     /// retrieves the font_atlas from the resources.
     pub(crate) fn font_atlas(&self) -> AtomicRefMut<FontAtlas> {
         self.get_resource_mut::<FontAtlas>()
@@ -391,19 +539,16 @@ pub struct InternalResources {
     storage: HashMap<ResourceTypeId, AtomicResourceCell>,
 }
 
-unsafe impl Send for InternalResources {}
-
-unsafe impl Sync for InternalResources {}
-
 impl InternalResources {
     fn remove_internal(&mut self, type_id: &ResourceTypeId) -> Option<Box<dyn Resource>> {
         self.storage.remove(type_id).map(|cell| cell.into_inner())
     }
 }
 
-pub trait Resource: 'static + Downcast {}
+/// Any type can be used as a resource, as long as it can be shared with the systems executed on other threads
+pub trait Resource: 'static + Downcast + Send + Sync {}
 
-impl<T> Resource for T where T: 'static {}
+impl<T> Resource for T where T: 'static + Send + Sync {}
 impl_downcast!(Resource);
 
 #[derive(Copy, Clone, Debug, Eq, PartialOrd, Ord)]
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
 use std::path::Path;
 
@@ -11,7 +12,7 @@ use crate::{
 
 
 /// Component used by the 2D Renderer to know which material to use when graphics a renderable object.
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum Material {
     /// Fill with a color
     Diffuse(Color),
@@ -41,16 +42,18 @@ pub(crate) struct Texture {
 
 impl Texture {
     pub fn from_png(file_path: &Path) -> Texture {
-        if let Ok(bytes) = read_file(file_path) {
-            let converted_image = image::load_from_memory_with_format(&bytes, ImageFormat::Png);
-            if let Ok(image) = converted_image {
-                return Texture::create_texture_from_dynamic_image(image);
-            }
+        if let Some(image) = load_png(file_path) {
+            return Texture::create_texture_from_dynamic_image(image);
         }
         log::error!("Error while loading your texture, loading fallback texture instead.");
         Texture::fallback_texture()
     }
 
+    /// Creates the texture from an image already loaded, for example by the scene assets preloading
+    pub(crate) fn from_image(image: DynamicImage) -> Texture {
+        Texture::create_texture_from_dynamic_image(image)
+    }
+
     pub fn from_color(color: &Color) -> Texture {
         let img = ImageBuffer::from_fn(1, 1, |_x, _y| {
             image::Rgba([color.red(), color.green(), color.blue(), (color.alpha() * 255.) as u8])
@@ -85,16 +88,18 @@ pub(crate) struct TextureArray {
 
 impl TextureArray {
     pub fn from_tileset(tileset: &Tileset) -> Self {
-        if let Ok(bytes) = read_file(Path::new(tileset.texture.as_str())) {
-            let converted_image = image::load_from_memory_with_format(&bytes, ImageFormat::Png);
-            if let Ok(image) = converted_image {
-                return Self::create_texture_array_from_dynamic_image(image, tileset.height as u32, tileset.width as u32, tileset.tile_width as u32, tileset.tile_height as u32);
-            }
+        if let Some(image) = load_png(Path::new(tileset.texture.as_str())) {
+            return Self::from_tileset_image(tileset, image);
         }
         log::error!("Error while loading your texture, loading fallback texture instead.");
         TextureArray::fallback_texture_array(tileset.height, tileset.width, tileset.tile_width as u32, tileset.tile_height as u32)
     }
 
+    /// Creates the texture array of `tileset` from its texture already loaded
+    pub(crate) fn from_tileset_image(tileset: &Tileset, image: DynamicImage) -> Self {
+        Self::create_texture_array_from_dynamic_image(image, tileset.height as u32, tileset.width as u32, tileset.tile_width as u32, tileset.tile_height as u32)
+    }
+
     fn create_texture_array_from_dynamic_image(mut dynamic_image: DynamicImage,
                                                nb_lines: u32,
                                                nb_columns: u32,
@@ -133,6 +138,12 @@ impl TextureArray {
     }
 }
 
+/// Reads and decodes the png image located at `file_path`
+pub(crate) fn load_png(file_path: &Path) -> Option<DynamicImage> {
+    let bytes = read_file(file_path).ok()?;
+    image::load_from_memory_with_format(&bytes, ImageFormat::Png).ok()
+}
+
 impl Display for TextureArray {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "array length : {}, unit width : {}, unit height : {}", self.bytes_array.len(), self.unit_width, self.unit_height)
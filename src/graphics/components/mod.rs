@@ -1,5 +1,7 @@
 //! Contains all the components provided by `Scion`
 
+use serde::{Deserialize, Serialize};
+
 pub use shapes::{square::Square, triangle::Triangle};
 
 pub mod animations;
@@ -7,9 +9,17 @@ pub mod color;
 pub mod material;
 pub mod shapes;
 pub mod tiles;
+pub mod tween;
 pub mod ui;
 
 /// Struct to add to any entity to 'hide' it during renderig
+#[derive(Serialize, Deserialize)]
 pub struct Hide;
 
 pub(crate) struct HidePropagated;
+
+/// Opacity applied to an entity during a scene transition
+pub(crate) struct TransitionOpacity(pub(crate) f32);
+
+/// Added to the entities of the scenes covered by a scene that doesn't render the scenes below it
+pub(crate) struct SceneHidden;
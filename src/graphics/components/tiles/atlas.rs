@@ -19,32 +19,37 @@ pub mod importer {
     use crate::core::resources::asset_manager::{AssetRef, AssetType};
     use crate::core::world::{GameData, Resources};
     use crate::utils::maths::Dimensions;
+    use crate::utils::ScionError;
 
     /// Import a tilemap from a .scion format located at `path`, into a TilemapAtlas
     pub fn import_tilemap(path: &str) -> TilemapAtlas {
-        match crate::utils::file::read_file(Path::new(path)) {
-            Ok(file) => {
-                let mut tilemap: TilemapAtlas = serde_json::from_slice(file.as_slice()).expect("");
-                tilemap.layers.iter_mut().for_each(|l| {
-                    let tiles: Vec<Vec<isize>> = serde_json::from_slice(BASE64_STANDARD.decode(l.tiles_encoded.as_ref().unwrap()).expect("").as_slice()).expect("");
-                    l.tiles = tiles;
-                    l.tiles_encoded = None;
-                });
-                debug!("Tilemap at path {} has been loaded", path);
-                tilemap
-            }
+        match try_import_tilemap(path) {
+            Ok(tilemap) => tilemap,
             Err(e) => panic!("{:?}", e)
         }
     }
 
+    /// Import a tilemap from a .scion format located at `path`, returning an error instead of panicking
+    pub(crate) fn try_import_tilemap(path: &str) -> Result<TilemapAtlas, ScionError> {
+        let file = crate::utils::file::read_file(Path::new(path))
+            .map_err(|e| ScionError::new(&format!("Impossible to read tilemap {}: {:?}", path, e)))?;
+        let mut tilemap: TilemapAtlas = serde_json::from_slice(file.as_slice())
+            .map_err(|e| ScionError::new(&format!("Impossible to parse tilemap {}: {:?}", path, e)))?;
+        for layer in tilemap.layers.iter_mut() {
+            let encoded = layer.tiles_encoded.take().ok_or_else(|| ScionError::new("Tilemap layer without tiles"))?;
+            let decoded = BASE64_STANDARD.decode(encoded)
+                .map_err(|e| ScionError::new(&format!("Impossible to decode tilemap {}: {:?}", path, e)))?;
+            layer.tiles = serde_json::from_slice(decoded.as_slice())
+                .map_err(|e| ScionError::new(&format!("Impossible to parse tilemap {}: {:?}", path, e)))?;
+        }
+        debug!("Tilemap at path {} has been loaded", path);
+        Ok(tilemap)
+    }
+
     /// Import a tileset from a .scion format located at `path`, into a TilesetAtlas
     pub fn import_tileset(path: &str) -> TilesetAtlas {
-        match crate::utils::file::read_file(Path::new(path)) {
-            Ok(file) => {
-                let tileset: TilesetAtlas = serde_json::from_slice(file.as_slice()).expect("");
-                debug!("Tileset at path {} has been loaded", path);
-                tileset
-            }
+        match try_import_tileset(path) {
+            Ok(tileset) => tileset,
             Err(e) => {
                 error!("{:?}", e);
                 std::panic::panic_any(e)
@@ -52,6 +57,16 @@ pub mod importer {
         }
     }
 
+    /// Import a tileset from a .scion format located at `path`, returning an error instead of panicking
+    pub(crate) fn try_import_tileset(path: &str) -> Result<TilesetAtlas, ScionError> {
+        let file = crate::utils::file::read_file(Path::new(path))
+            .map_err(|e| ScionError::new(&format!("Impossible to read tileset {}: {:?}", path, e)))?;
+        let tileset: TilesetAtlas = serde_json::from_slice(file.as_slice())
+            .map_err(|e| ScionError::new(&format!("Impossible to parse tileset {}: {:?}", path, e)))?;
+        debug!("Tileset at path {} has been loaded", path);
+        Ok(tileset)
+    }
+
 
     /// Load a tilemap into the world.
     /// To use this function, you need to have an entry into the registry for the `AssetType::Tilemap(name)` (see `AssetManager`)
@@ -60,8 +75,11 @@ pub mod importer {
     pub fn load_tilemap(data: &mut GameData, name: &str, tilemap_transform: Transform) -> (TilemapAtlas, Entity) {
         let (subworld, resources) = data.split();
 
-        let tilemap_path = resources.assets().get_atlas_path_for_asset_type(AssetType::Tilemap(name.to_string()));
-        let tilemap = import_tilemap(&tilemap_path);
+        let preloaded_tilemap = resources.assets_mut().take_preloaded_tilemap(name);
+        let tilemap = match preloaded_tilemap {
+            Some(tilemap) => tilemap,
+            None => import_tilemap(&resources.assets().get_atlas_path_for_asset_type(AssetType::Tilemap(name.to_string()))),
+        };
 
         if tilemap.tilesets.len() > 1 {
             panic!("Multiple tilesets not yet implemented !");
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
 use wgpu::{PrimitiveTopology, util::BufferInitDescriptor};
@@ -16,18 +17,25 @@ use crate::utils::maths::Vector;
 const INDICES: &[u16] = &[0, 1, 3, 3, 1, 2];
 
 /// Renderable Sprite.
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Sprite {
     /// Desired tile to render for this material.
     tile_number: usize,
     /// Current computed content for vertex
+    #[serde(skip)]
     contents: Option<[TexturedGlVertexWithLayer; 4]>,
     /// Flag to keep track of changed tile number
+    #[serde(default)]
     dirty: bool,
     /// Pivot point of the sprite, default topleft
+    #[serde(default = "default_pivot")]
     pivot: Pivot,
 }
 
+fn default_pivot() -> Pivot {
+    Pivot::TopLeft
+}
+
 impl Sprite {
     /// Creates a new sprite that will use the `tile_number` from the tileset associated in the same
     /// entity
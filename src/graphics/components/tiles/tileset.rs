@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
@@ -6,7 +7,7 @@ use log::error;
 use crate::graphics::components::tiles::atlas::data::{TileConfig, TilesetAtlas};
 use crate::utils::file::read_file;
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 /// Struct representing a tileset definition.
 pub struct Tileset {
     pub(crate) name: String,
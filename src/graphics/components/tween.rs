@@ -0,0 +1,476 @@
+//! Tweens interpolate a property of a component toward a target value, following an easing curve.
+
+use std::f32::consts::PI;
+use std::time::Duration;
+
+use hecs::Component;
+
+use crate::core::components::maths::transform::Transform;
+use crate::graphics::components::color::Color;
+use crate::graphics::components::material::Material;
+use crate::utils::maths::Vector;
+
+/// Easing curves, giving the progress of a tween from its linear progress. See <https://easings.net>.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Easing {
+    /// Returns the eased progress for the linear progress `t`, between 0 and 1.
+    /// Back and elastic curves go slightly beyond this range.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0., 1.);
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t.powi(2),
+            Easing::QuadOut => ease_out(t, |t| t.powi(2)),
+            Easing::QuadInOut => ease_in_out(t, |t| t.powi(2)),
+            Easing::CubicIn => t.powi(3),
+            Easing::CubicOut => ease_out(t, |t| t.powi(3)),
+            Easing::CubicInOut => ease_in_out(t, |t| t.powi(3)),
+            Easing::QuartIn => t.powi(4),
+            Easing::QuartOut => ease_out(t, |t| t.powi(4)),
+            Easing::QuartInOut => ease_in_out(t, |t| t.powi(4)),
+            Easing::QuintIn => t.powi(5),
+            Easing::QuintOut => ease_out(t, |t| t.powi(5)),
+            Easing::QuintInOut => ease_in_out(t, |t| t.powi(5)),
+            Easing::SineIn => sine_in(t),
+            Easing::SineOut => ease_out(t, sine_in),
+            Easing::SineInOut => ease_in_out(t, sine_in),
+            Easing::ExpoIn => expo_in(t),
+            Easing::ExpoOut => ease_out(t, expo_in),
+            Easing::ExpoInOut => ease_in_out(t, expo_in),
+            Easing::CircIn => circ_in(t),
+            Easing::CircOut => ease_out(t, circ_in),
+            Easing::CircInOut => ease_in_out(t, circ_in),
+            Easing::BackIn => back_in(t),
+            Easing::BackOut => ease_out(t, back_in),
+            Easing::BackInOut => ease_in_out(t, back_in),
+            Easing::ElasticIn => elastic_in(t),
+            Easing::ElasticOut => ease_out(t, elastic_in),
+            Easing::ElasticInOut => ease_in_out(t, elastic_in),
+            Easing::BounceIn => ease_out(t, bounce_out),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => ease_in_out(t, |t| 1. - bounce_out(1. - t)),
+        }
+    }
+}
+
+/// Mirrors the ease in curve `ease_in`
+fn ease_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    1. - ease_in(1. - t)
+}
+
+/// Uses the ease in curve `ease_in` on the first half, and its mirror on the second half
+fn ease_in_out(t: f32, ease_in: impl Fn(f32) -> f32) -> f32 {
+    if t < 0.5 {
+        ease_in(t * 2.) / 2.
+    } else {
+        1. - ease_in((1. - t) * 2.) / 2.
+    }
+}
+
+fn sine_in(t: f32) -> f32 {
+    1. - (t * PI / 2.).cos()
+}
+
+fn expo_in(t: f32) -> f32 {
+    if t == 0. {
+        0.
+    } else {
+        2_f32.powf(10. * t - 10.)
+    }
+}
+
+fn circ_in(t: f32) -> f32 {
+    1. - (1. - t.powi(2)).sqrt()
+}
+
+fn back_in(t: f32) -> f32 {
+    const OVERSHOOT: f32 = 1.70158;
+    (OVERSHOOT + 1.) * t.powi(3) - OVERSHOOT * t.powi(2)
+}
+
+fn elastic_in(t: f32) -> f32 {
+    if t == 0. || t == 1. {
+        t
+    } else {
+        -(2_f32.powf(10. * t - 10.)) * ((t * 10. - 10.75) * 2. * PI / 3.).sin()
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1. / D {
+        N * t * t
+    } else if t < 2. / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+/// Values that can be interpolated by a tween
+pub trait Lerp: Copy + Send + Sync + 'static {
+    /// Value at `t` between `self` (t = 0) and `target` (t = 1)
+    fn lerp(&self, target: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(&self, target: &Self, t: f32) -> Self {
+        self + (target - self) * t
+    }
+}
+
+impl Lerp for Vector {
+    fn lerp(&self, target: &Self, t: f32) -> Self {
+        Vector::new(self.x.lerp(&target.x, t), self.y.lerp(&target.y, t))
+    }
+}
+
+/// A `Lens` gives a tween access to a property of a component. Implement it to tween your own components,
+/// and register it with `ScionBuilder::with_tween_lens`.
+/// ```
+/// use scion::graphics::components::tween::Lens;
+///
+/// struct Health(f32);
+///
+/// struct HealthLens;
+///
+/// impl Lens for HealthLens {
+///     type Component = Health;
+///     type Value = f32;
+///
+///     fn get(&self, component: &Health) -> f32 {
+///         component.0
+///     }
+///
+///     fn set(&self, component: &mut Health, value: f32) {
+///         component.0 = value;
+///     }
+/// }
+/// ```
+pub trait Lens: Send + Sync + 'static {
+    type Component: Component;
+    type Value: Lerp;
+
+    /// Current value of the property
+    fn get(&self, component: &Self::Component) -> Self::Value;
+
+    /// Changes the value of the property
+    fn set(&self, component: &mut Self::Component, value: Self::Value);
+}
+
+/// Local position of a `Transform`
+pub struct TransformPosition;
+
+/// Position of a camera, which is the local position of the `Transform` of its entity
+pub type CameraPosition = TransformPosition;
+
+impl Lens for TransformPosition {
+    type Component = Transform;
+    type Value = Vector;
+
+    fn get(&self, component: &Transform) -> Vector {
+        Vector::new(component.translation().x(), component.translation().y())
+    }
+
+    fn set(&self, component: &mut Transform, value: Vector) {
+        component.set_x(value.x);
+        component.set_y(value.y);
+    }
+}
+
+/// Scale of a `Transform`
+pub struct TransformScale;
+
+impl Lens for TransformScale {
+    type Component = Transform;
+    type Value = f32;
+
+    fn get(&self, component: &Transform) -> f32 {
+        component.scale
+    }
+
+    fn set(&self, component: &mut Transform, value: f32) {
+        component.set_scale(value);
+    }
+}
+
+/// Local angle of a `Transform`
+pub struct TransformAngle;
+
+impl Lens for TransformAngle {
+    type Component = Transform;
+    type Value = f32;
+
+    fn get(&self, component: &Transform) -> f32 {
+        component.local_angle
+    }
+
+    fn set(&self, component: &mut Transform, value: f32) {
+        component.set_angle(value);
+    }
+}
+
+/// Alpha of the color of a `Material::Diffuse`. Other materials are left untouched.
+pub struct ColorAlpha;
+
+impl Lens for ColorAlpha {
+    type Component = Material;
+    type Value = f32;
+
+    fn get(&self, component: &Material) -> f32 {
+        match component {
+            Material::Diffuse(color) => color.alpha(),
+            _ => 1.,
+        }
+    }
+
+    fn set(&self, component: &mut Material, value: f32) {
+        if let Material::Diffuse(color) = component {
+            let alpha = value.clamp(0., 1.);
+            color.replace(Color::new(color.red(), color.green(), color.blue(), alpha));
+        }
+    }
+}
+
+/// Number of times a tween is played
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Repeat {
+    Count(u32),
+    Forever,
+}
+
+/// Component interpolating the property targeted by the lens `L` of its entity, from its value when the tween starts
+/// to a target value. Tweens are played by the engine, using the scaled time.
+/// ```no_run
+/// use std::time::Duration;
+/// use scion::core::components::maths::transform::Transform;
+/// use scion::core::world::{GameData, World};
+/// use scion::graphics::components::tween::{Easing, TransformPosition, TransformScale, Tween};
+/// use scion::utils::maths::Vector;
+///
+/// fn spawn_coin(data: &mut GameData) {
+///     data.push((
+///         Transform::from_xy(100., 100.),
+///         Tween::new(TransformPosition, Vector::new(100., 80.), Duration::from_millis(400))
+///             .with_easing(Easing::QuadOut)
+///             .with_yoyo()
+///             .repeat_forever(),
+///         Tween::new(TransformScale, 2., Duration::from_secs(1))
+///             .with_easing(Easing::ElasticOut)
+///             .with_delay(Duration::from_millis(500)),
+///     ));
+/// }
+/// ```
+pub struct Tween<L: Lens> {
+    lens: L,
+    from: Option<L::Value>,
+    to: L::Value,
+    duration: Duration,
+    easing: Easing,
+    delay: Duration,
+    repeat: Repeat,
+    yoyo: bool,
+    /// Elapsed time in the current play
+    elapsed: Duration,
+    /// Number of plays completed
+    completed_plays: u32,
+    finished: bool,
+}
+
+impl<L: Lens> Tween<L> {
+    /// Creates a linear tween of the property targeted by `lens` toward `to`, during `duration`
+    pub fn new(lens: L, to: L::Value, duration: Duration) -> Self {
+        assert!(!duration.is_zero(), "The duration of a tween must be greater than zero");
+        Self {
+            lens,
+            from: None,
+            to,
+            duration,
+            easing: Easing::Linear,
+            delay: Duration::ZERO,
+            repeat: Repeat::Count(1),
+            yoyo: false,
+            elapsed: Duration::ZERO,
+            completed_plays: 0,
+            finished: false,
+        }
+    }
+
+    /// Starts the tween from `from` instead of the value of the property when it starts
+    pub fn with_from(mut self, from: L::Value) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    /// Sets the easing curve of the tween
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Waits `delay` before starting the tween
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Plays the tween `count` times
+    pub fn with_repeat_count(mut self, count: u32) -> Self {
+        self.repeat = Repeat::Count(count.max(1));
+        self
+    }
+
+    /// Plays the tween until it is removed
+    pub fn repeat_forever(mut self) -> Self {
+        self.repeat = Repeat::Forever;
+        self
+    }
+
+    /// Plays the tween backward every other time, going back to its start value
+    pub fn with_yoyo(mut self) -> Self {
+        self.yoyo = true;
+        self
+    }
+
+    /// Returns whether the tween has been played the requested number of times
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Advances the tween by `delta`, and applies the new value to `component`
+    pub(crate) fn advance(&mut self, delta: Duration, component: &mut L::Component) {
+        if self.finished || delta.is_zero() {
+            return;
+        }
+        let mut delta = delta;
+        if !self.delay.is_zero() {
+            if delta < self.delay {
+                self.delay -= delta;
+                return;
+            }
+            delta -= self.delay;
+            self.delay = Duration::ZERO;
+        }
+        let lens = &self.lens;
+        let from = *self.from.get_or_insert_with(|| lens.get(component));
+
+        self.elapsed += delta;
+        while self.elapsed >= self.duration && !self.finished {
+            self.completed_plays += 1;
+            match self.repeat {
+                Repeat::Count(count) if self.completed_plays >= count => {
+                    self.finished = true;
+                    self.elapsed = self.duration;
+                }
+                _ => self.elapsed -= self.duration,
+            }
+        }
+
+        let progress = self.elapsed.as_secs_f32() / self.duration.as_secs_f32();
+        let backward = self.yoyo && (self.completed_plays - self.finished as u32) % 2 == 1;
+        let progress = if backward { 1. - progress } else { progress };
+        self.lens.set(component, from.lerp(&self.to, self.easing.apply(progress)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(expected: f32, value: f32) -> bool {
+        (expected - value).abs() < 0.0001
+    }
+
+    #[test]
+    fn easing_test() {
+        let all = [
+            Easing::Linear, Easing::QuadIn, Easing::QuadOut, Easing::QuadInOut, Easing::CubicIn, Easing::CubicOut,
+            Easing::CubicInOut, Easing::QuartIn, Easing::QuartOut, Easing::QuartInOut, Easing::QuintIn,
+            Easing::QuintOut, Easing::QuintInOut, Easing::SineIn, Easing::SineOut, Easing::SineInOut, Easing::ExpoIn,
+            Easing::ExpoOut, Easing::ExpoInOut, Easing::CircIn, Easing::CircOut, Easing::CircInOut, Easing::BackIn,
+            Easing::BackOut, Easing::BackInOut, Easing::ElasticIn, Easing::ElasticOut, Easing::ElasticInOut,
+            Easing::BounceIn, Easing::BounceOut, Easing::BounceInOut,
+        ];
+        for easing in all {
+            assert!(approx(0., easing.apply(0.)), "{:?} doesn't start at 0", easing);
+            assert!(approx(1., easing.apply(1.)), "{:?} doesn't end at 1", easing);
+        }
+        assert!(approx(0.25, Easing::QuadIn.apply(0.5)));
+        assert!(approx(0.75, Easing::QuadOut.apply(0.5)));
+        assert!(approx(0.5, Easing::CubicInOut.apply(0.5)));
+        assert!(Easing::BackIn.apply(0.2) < 0.);
+        assert!(Easing::ElasticOut.apply(0.2) > 1.);
+    }
+
+    #[test]
+    fn tween_delay_yoyo_and_repeat_test() {
+        let mut transform = Transform::from_xy(10., 0.);
+        let mut tween = Tween::new(TransformPosition, Vector::new(20., 10.), Duration::from_secs(1))
+            .with_delay(Duration::from_millis(500))
+            .with_yoyo()
+            .with_repeat_count(2);
+
+        tween.advance(Duration::from_millis(400), &mut transform);
+        assert_eq!(10., transform.translation().x());
+        transform.set_x(0.);
+        tween.advance(Duration::from_millis(600), &mut transform);
+        assert!(approx(10., transform.translation().x()) && approx(5., transform.translation().y()));
+        tween.advance(Duration::from_millis(750), &mut transform);
+        assert!(approx(15., transform.translation().x()) && approx(7.5, transform.translation().y()));
+        assert!(!tween.is_finished());
+        tween.advance(Duration::from_millis(1000), &mut transform);
+        assert!(tween.is_finished());
+        assert!(approx(0., transform.translation().x()) && approx(0., transform.translation().y()));
+    }
+
+    #[test]
+    fn color_alpha_lens_test() {
+        let mut material = Material::Diffuse(Color::new(10, 20, 30, 1.));
+        let mut tween = Tween::new(ColorAlpha, 0., Duration::from_secs(2)).with_easing(Easing::QuadIn);
+        tween.advance(Duration::from_secs(1), &mut material);
+        assert!(approx(0.75, ColorAlpha.get(&material)));
+        tween.advance(Duration::from_secs(5), &mut material);
+        assert!(tween.is_finished());
+        assert_eq!(0., ColorAlpha.get(&material));
+    }
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use std::ops::Range;
 
 use wgpu::{PrimitiveTopology, util::BufferInitDescriptor};
@@ -15,33 +16,62 @@ use crate::core::resources::asset_manager::AssetRef;
 use crate::core::world::Resources;
 
 /// A component representing a Text in the UI.
+#[derive(Serialize, Deserialize)]
 pub struct UiText {
     text: String,
     font_ref: AssetRef<Font>,
     /// font size when using a TrueType font
+    #[serde(default = "default_font_size")]
     font_size: usize,
     /// font color when using a TrueType font
+    #[serde(default)]
     font_color: Option<Color>,
     /// Optional text settings when used in buttons
+    #[serde(default)]
     padding: Padding,
+    #[serde(skip, default = "default_dirty")]
     pub(crate) dirty: bool,
-    pub(crate) sync_fn: Option<fn(&mut Resources) -> String>
+    #[serde(skip)]
+    pub(crate) sync_fn: Option<fn(&mut Resources) -> String>,
+    /// Key of the `GameState` value displayed by this text
+    pub(crate) sync_key: Option<String>,
+    /// Whether or not the text already displays the value of `sync_key`
+    #[serde(skip)]
+    pub(crate) key_synced: bool,
+}
+
+/// Restored texts need to be rendered again
+fn default_dirty() -> bool {
+    true
+}
+
+fn default_font_size() -> usize {
+    10
 }
 
 impl UiText {
     /// Creates a new `UiText` with `text` as default content and `font`
     pub fn new(text: String, font_ref: AssetRef<Font>) -> Self {
-        Self { text, font_ref, dirty: true, font_size: 10, font_color: None, sync_fn: None, padding: Padding::default() }
+        Self { text, font_ref, dirty: true, font_size: 10, font_color: None, sync_fn: None, sync_key: None, key_synced: false, padding: Padding::default() }
     }
 
     /// provide a fn that will automatically synchronize the text
     /// with the given value
+    /// The function isn't part of the saved snapshots, a restored text is no longer synchronized
     pub fn sync_value(mut self, sync_function: fn(&mut Resources) -> String) -> Self
     {
         self.sync_fn = Some(sync_function);
         self
     }
 
+    /// synchronizes the text with the `GameState` value stored for `key`. Unlike [`UiText::sync_value`],
+    /// the text is only updated when the value changes
+    pub fn sync_state(mut self, key: &str) -> Self {
+        self.sync_key = Some(key.to_string());
+        self.key_synced = false;
+        self
+    }
+
     /// retrieves the content of this `UiText`
     pub fn text(&self) -> &String {
         &self.text
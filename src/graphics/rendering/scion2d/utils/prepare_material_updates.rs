@@ -6,6 +6,7 @@ use std::time::SystemTime;
 use crate::graphics::components::color::Color;
 use crate::graphics::components::material::{Material, Texture, TextureArray};
 use crate::graphics::components::tiles::tileset::Tileset;
+use crate::core::systems::HOT_RELOAD_TIMER;
 use crate::core::world::{GameData, World};
 use crate::graphics::rendering::{DiffuseBindGroupUpdate, RenderingUpdate};
 use crate::graphics::rendering::scion2d::pre_renderer::Scion2DPreRenderer;
@@ -32,7 +33,7 @@ pub(crate) fn call(renderer: &mut Scion2DPreRenderer, data: &mut GameData) -> Ve
                 }
             }
             Material::Tileset(tileset) => {
-                if let Some(update) = try_tileset_update(renderer, hot_timer_cycle, tileset){
+                if let Some(update) = try_tileset_update(renderer, data, hot_timer_cycle, tileset){
                     updates.push(update);
                 }
             }
@@ -55,9 +56,11 @@ fn try_texture_update(renderer: &mut Scion2DPreRenderer, data: &GameData, hot_ti
     let new_timestamp = read_modification_timestamp(renderer, hot_timer_cycle, texture_path);
     if renderer.should_reload_texture(texture_path, &new_timestamp) {
         let path = Path::new(texture_path);
-        let loaded_texture = match data.font_atlas().get_texture_from_path(texture_path) {
-            Some(t) => t.take_texture(),
-            None => Texture::from_png(path)
+        let preloaded_image = data.assets_mut().take_preloaded_image(texture_path);
+        let loaded_texture = match (data.font_atlas().get_texture_from_path(texture_path), preloaded_image) {
+            (Some(t), _) => t.take_texture(),
+            (None, Some(image)) => Texture::from_image(image),
+            (None, None) => Texture::from_png(path)
         };
         let update = RenderingUpdate::DiffuseBindGroup { path: texture_path.to_string(), data: DiffuseBindGroupUpdate::TextureBindGroup(loaded_texture) };
         let timestamp_to_use = if let Some(Ok(timestamp)) = new_timestamp {
@@ -71,11 +74,15 @@ fn try_texture_update(renderer: &mut Scion2DPreRenderer, data: &GameData, hot_ti
     None
 }
 
-fn try_tileset_update(renderer: &mut Scion2DPreRenderer, hot_timer_cycle: bool, tileset: &Tileset) -> Option<RenderingUpdate> {
+fn try_tileset_update(renderer: &mut Scion2DPreRenderer, data: &GameData, hot_timer_cycle: bool, tileset: &Tileset) -> Option<RenderingUpdate> {
     let new_timestamp = read_modification_timestamp(renderer, hot_timer_cycle, tileset.texture.as_str());
     if renderer.should_reload_texture(tileset.texture.as_str(), &new_timestamp) {
         let _path = Path::new(tileset.texture.as_str());
-        let update = RenderingUpdate::DiffuseBindGroup{ path: tileset.texture.to_string(), data: DiffuseBindGroupUpdate::TilesetBindGroup(TextureArray::from_tileset(tileset))};
+        let texture_array = match data.assets_mut().take_preloaded_image(tileset.texture.as_str()) {
+            Some(image) => TextureArray::from_tileset_image(tileset, image),
+            None => TextureArray::from_tileset(tileset)
+        };
+        let update = RenderingUpdate::DiffuseBindGroup{ path: tileset.texture.to_string(), data: DiffuseBindGroupUpdate::TilesetBindGroup(texture_array)};
         let timestamp_to_use = if let Some(Ok(timestamp)) = new_timestamp {
             timestamp
         } else {
@@ -101,7 +108,7 @@ fn should_try_to_hot_reload(data: &mut GameData) -> bool {
     if cfg!(feature = "hot-reload") {
         let mut timers = data.timers();
         let hot_reload_timer =
-            timers.get_timer("hot-reload-timer").expect("Missing mandatory timer : hot_reload");
+            timers.get_timer(HOT_RELOAD_TIMER).expect("Missing mandatory timer : hot_reload");
         hot_reload_timer.cycle() > 0
     } else {
         false
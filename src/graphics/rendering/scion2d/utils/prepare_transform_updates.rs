@@ -1,6 +1,6 @@
 use hecs::Component;
 
-use crate::graphics::components::{Square, Triangle};
+use crate::graphics::components::{Square, TransitionOpacity, Triangle};
 use crate::graphics::components::material::Material;
 use crate::core::components::maths::camera::Camera;
 use crate::core::components::maths::transform::Transform;
@@ -46,14 +46,15 @@ fn update_transforms_for_type<T: Component + Renderable2D>(
         (c, t)
     };
     let camera = (&camera1.0, &camera1.1);
-    for (entity, (transform, optional_ui_component, renderable, optional_material)) in
-    data.query::<(&Transform, Option<&UiComponent>, &T, Option<&Material>)>().iter() {
+    for (entity, (transform, optional_ui_component, renderable, optional_material, optional_opacity)) in
+    data.query::<(&Transform, Option<&UiComponent>, &T, Option<&Material>, Option<&TransitionOpacity>)>().iter() {
         // TODO : update only if needed ?
         let uniform = GlUniform::from(UniformData {
             transform,
             camera,
             is_ui_component: optional_ui_component.is_some(),
             pivot_offset: renderable.get_pivot_offset(optional_material),
+            opacity: optional_opacity.map_or(1., |opacity| opacity.0),
         });
         updates.push(RenderingUpdate::TransformUniform {
             entity,
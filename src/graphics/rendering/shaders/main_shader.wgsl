@@ -6,6 +6,7 @@ struct VertexOutput {
 struct Uniforms {
     model_trans: mat4x4<f32>,
     camera_view: mat4x4<f32>,
+    opacity: vec4<f32>,
 }
 
 @group(0)
@@ -33,5 +34,6 @@ var s_diffuse: sampler;
 
 @fragment
 fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(t_diffuse, s_diffuse, vertex.v_tex_translation);
+    let color = textureSample(t_diffuse, s_diffuse, vertex.v_tex_translation);
+    return vec4<f32>(color.rgb, color.a * r_data.opacity.x);
 }
\ No newline at end of file
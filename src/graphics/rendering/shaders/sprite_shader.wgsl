@@ -6,7 +6,8 @@ struct VertexOutput {
 
 struct Uniforms {
     model_trans: mat4x4<f32>,
-    camera_view: mat4x4<f32>
+    camera_view: mat4x4<f32>,
+    opacity: vec4<f32>,
 }
 
 @group(0)
@@ -37,5 +38,6 @@ var s_diffuse: sampler;
 // Définit la sortie du shader.
 @fragment
 fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
-    return textureSample(t_diffuse, s_diffuse, vertex.v_tex_translation, vertex.layer);
+    let color = textureSample(t_diffuse, s_diffuse, vertex.v_tex_translation, vertex.layer);
+    return vec4<f32>(color.rgb, color.a * r_data.opacity.x);
 }
\ No newline at end of file
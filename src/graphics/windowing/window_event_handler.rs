@@ -1,3 +1,5 @@
+use std::sync::mpsc::TryRecvError;
+
 use winit::event::WindowEvent;
 use winit::keyboard::Key;
 use crate::core::components::maths::camera::Camera;
@@ -12,12 +14,22 @@ pub fn handle_window_event(runner: &mut ScionRunner) -> Vec<RendererEvent> {
     let mut update = vec![];
     let mut force_redraw = false;
     if let Some(receiver) = runner.main_thread_receiver.as_ref() {
-        while let Ok(event) = receiver.try_recv() {
+        loop {
+            let event = match receiver.try_recv() {
+                Ok(event) => event,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    // The main thread is gone, so the game loop has to stop
+                    runner.running = false;
+                    break;
+                }
+            };
             if event.redraw {
                 force_redraw = true;
             };
             if let Some(window_event) = event.window_event {
                 match window_event {
+                    WindowEvent::CloseRequested => runner.game_data.window().request_close(),
                     WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
                         update.push(RendererEvent::Resize(runner.window.as_ref().expect("Missing window").inner_size(), scale_factor));
                         for (_, camera) in runner.game_data.query_mut::<&mut Camera>() {
@@ -38,6 +38,28 @@ pub fn open_file(path: &Path) -> Result<File, FileReaderError> {
     }
 }
 
+/// This will give you the directory where the application `app_name` can store its user data:
+/// `%APPDATA%\<app_name>` on Windows, `~/Library/Application Support/<app_name>` on macOS and
+/// `$XDG_DATA_HOME/<app_name>` (defaulting to `~/.local/share/<app_name>`) elsewhere.
+pub fn app_data_path(app_name: &str) -> PathBuf {
+    let app_directory: String = app_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    let home = || env::var_os("HOME").map(PathBuf::from).unwrap_or_default();
+    let data_directory = if cfg!(target_os = "windows") {
+        env::var_os("APPDATA").map(PathBuf::from).unwrap_or_else(home)
+    } else if cfg!(target_os = "macos") {
+        home().join("Library").join("Application Support")
+    } else {
+        env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .unwrap_or_else(|| home().join(".local").join("share"))
+    };
+    data_directory.join(app_directory.trim())
+}
+
 /// This will give you the path to the executable (when in build mode) or to the root of the current project.
 pub fn app_base_path() -> PathBuilder {
     if let Some(manifest_dir) = env::var_os("CARGO_MANIFEST_DIR") {
@@ -30,6 +30,9 @@ impl Default for FrameLimiterConfig {
     }
 }
 
+/// Maximum number of fixed steps executed in a single frame, to avoid spiraling when the game can't keep up
+const MAX_FIXED_STEPS_PER_FRAME: u32 = 5;
+
 pub(crate) struct FrameLimiter {
     strategy: FrameLimiterStrategy,
     target_render_duration: Duration,
@@ -37,11 +40,11 @@ pub(crate) struct FrameLimiter {
     pub(crate) min_tick_duration: Duration,
     last_render_frame_start: Instant,
     last_tick_start: Instant,
-    last_fixed_tick_start: Instant,
+    fixed_accumulator: Duration,
 }
 
 impl FrameLimiter {
-    pub fn new(config: FrameLimiterConfig) -> FrameLimiter {
+    pub fn new(config: FrameLimiterConfig, fixed_delta_duration: Duration) -> FrameLimiter {
         let target_frame_duration = {
             match config.strategy {
                 FrameLimiterStrategy::Unlimited => Duration::from_secs(0),
@@ -56,20 +59,17 @@ impl FrameLimiter {
         Self {
             strategy: config.strategy,
             target_render_duration: target_frame_duration,
-            target_fixed_duration:  Duration::from_secs(1) / 60,
+            target_fixed_duration: fixed_delta_duration,
             min_tick_duration:  Duration::from_secs(1) / 60,
             last_render_frame_start: Instant::now(),
-            last_fixed_tick_start: Instant::now(),
             last_tick_start: Instant::now(),
+            fixed_accumulator: Duration::ZERO,
         }
     }
 
     pub fn render(&mut self) {
         self.last_render_frame_start = Instant::now();
     }
-    pub fn fixed_tick(&mut self) {
-        self.last_fixed_tick_start = Instant::now();
-    }
     pub fn tick(&mut self, instant: &Instant) {
         self.last_tick_start = instant.clone();
     }
@@ -92,15 +92,19 @@ impl FrameLimiter {
         }
     }
 
-    pub fn is_fixed_update(&mut self) -> bool {
-        let frame_start = self.last_fixed_tick_start;
-        let target_frame_duration = self.target_fixed_duration;
-        let elapsed = Instant::now() - frame_start;
-        if elapsed < target_frame_duration {
-            false
-        } else {
-            true
+    /// Accumulates `elapsed` and returns the number of fixed steps to execute to catch up with it.
+    pub fn accumulate_fixed_steps(&mut self, elapsed: Duration) -> u32 {
+        self.fixed_accumulator += elapsed;
+        let mut steps = 0;
+        while self.fixed_accumulator >= self.target_fixed_duration && steps < MAX_FIXED_STEPS_PER_FRAME {
+            self.fixed_accumulator -= self.target_fixed_duration;
+            steps += 1;
+        }
+        if self.fixed_accumulator >= self.target_fixed_duration {
+            // Too late to catch up, the remaining time is dropped
+            self.fixed_accumulator = Duration::ZERO;
         }
+        steps
     }
 
     pub fn is_min_tick(&mut self) -> bool {
@@ -114,4 +118,28 @@ impl FrameLimiter {
             true
         }
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_steps_catch_up_test() {
+        let mut limiter = FrameLimiter::new(FrameLimiterConfig::default(), Duration::from_millis(10));
+        assert_eq!(3, limiter.accumulate_fixed_steps(Duration::from_millis(35)));
+        assert_eq!(Duration::from_millis(5), limiter.fixed_accumulator);
+
+        assert_eq!(MAX_FIXED_STEPS_PER_FRAME, limiter.accumulate_fixed_steps(Duration::from_secs(1)));
+        assert_eq!(Duration::ZERO, limiter.fixed_accumulator);
+    }
+
+    #[test]
+    fn fixed_steps_keep_remainder_at_max_steps_test() {
+        let mut limiter = FrameLimiter::new(FrameLimiterConfig::default(), Duration::from_millis(10));
+        let steps = MAX_FIXED_STEPS_PER_FRAME;
+        assert_eq!(steps, limiter.accumulate_fixed_steps(Duration::from_millis(10 * steps as u64 + 4)));
+        assert_eq!(Duration::from_millis(4), limiter.fixed_accumulator);
+        assert_eq!(1, limiter.accumulate_fixed_steps(Duration::from_millis(6)));
+    }
+}
@@ -54,7 +54,7 @@ impl Dimensions {
 }
 
 /// Struct used in all `Scion` to specify any 2D movement.
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct Vector {
     pub(crate) x: f32,
     pub(crate) y: f32,